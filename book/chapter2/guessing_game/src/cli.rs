@@ -0,0 +1,793 @@
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+
+use crate::contest::{Contest, Mode, Standing};
+use crate::domain::{Domain, Round};
+use crate::game::{Game, Outcome, State};
+use crate::input::{self, Line};
+use crate::interval::Interval;
+use crate::grid::{Answer as GridAnswer, Clues, Grid, Point};
+use crate::liar::{self, Noise, NoisyGame, Ulam};
+use crate::mastermind::{Feedback, Knuth, Mastermind};
+use crate::report::Reporter;
+use crate::reverse::{Answer, Contradiction, Reverse};
+use crate::save::SaveTarget;
+use crate::strategy::{Strategy, MAX_GUESSES};
+use crate::timer::Timer;
+use crate::turns::Turns;
+use crate::wordle::{self, Style, Wordle};
+
+// Plays a round of `game` by reading one guess per line from `input` and
+// telling `reporter` what happened, until the player has won, run out of
+// attempts, time or input. `timer` is started at the first prompt.
+//
+// Instead of a guess the player can type one of the commands in
+// `input::COMMANDS`. `save` saves the round to `save` and returns with the
+// game still playing.
+pub fn play<R: BufRead>(
+    game: &mut Game,
+    mut input: R,
+    reporter: &mut dyn Reporter,
+    timer: &mut Timer,
+    save: Option<&SaveTarget>,
+) -> io::Result<State> {
+    reporter.start(game)?;
+    timer.start();
+
+    while game.state() == State::Playing {
+        if let Some(left) = timer.remaining() {
+            reporter.time_left(left)?;
+        }
+        reporter.prompt(game)?;
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            game.give_up();
+            break;
+        }
+
+        // A guess that comes in after the time is up does not count.
+        if timer.is_up() {
+            game.run_out_of_time();
+            break;
+        }
+
+        let guess = match input::parse(&line, game.range()) {
+            Ok(Line::Guess(guess)) => guess,
+            Ok(Line::Quit) => {
+                game.give_up();
+                break;
+            }
+            Ok(Line::Hint(hint)) => {
+                match game.hint(hint) {
+                    Ok(clue) => reporter.hint(game, &clue)?,
+                    Err(reason) => reporter.no_hint(game, reason)?,
+                }
+                continue;
+            }
+            Ok(Line::History) => {
+                reporter.history(game)?;
+                continue;
+            }
+            Ok(Line::Help) => {
+                reporter.help()?;
+                continue;
+            }
+            Ok(Line::Save) => match save {
+                Some(target) => {
+                    target.save(game, timer.elapsed())?;
+                    reporter.saved(&target.path)?;
+                    return Ok(game.state());
+                }
+                None => {
+                    reporter.help()?;
+                    continue;
+                }
+            },
+            Err(error) => {
+                reporter.invalid(game, line.trim(), &error)?;
+                continue;
+            }
+        };
+
+        timer.split();
+        let outcome = game.guess(guess);
+        reporter.guessed(game, guess, outcome)?;
+    }
+
+    timer.stop();
+    reporter.finished(game)?;
+
+    Ok(game.state())
+}
+
+// A round played one line at a time, for the modes that write plain text
+// instead of going through a `Reporter`. `prompted` reads the lines and
+// takes care of `quit`, `history` and `help`, and the mode of everything
+// else.
+trait Prompted {
+    fn state(&self) -> State;
+    fn give_up(&mut self);
+
+    fn prompt(&self) -> String {
+        "Please input your guess:".to_string()
+    }
+
+    // What a guess looks like, for `help`, e.g. "a word".
+    fn example(&self) -> String;
+
+    // Explains the answers, for `help`.
+    fn explain(&self, _output: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()>;
+
+    // Whether `line` is a guess even though it reads like a command, like
+    // the letter `q` or the word "help" in a dictionary.
+    fn is_guess(&self, _line: &str) -> bool {
+        false
+    }
+
+    // Takes a line that is no command and tells the player what came of it.
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()>;
+}
+
+// Reads one line at a time from `input` until the round is over or the input
+// runs out.
+fn prompted<P: Prompted, R: BufRead>(mode: &mut P, mut input: R, output: &mut dyn Write) -> io::Result<()> {
+    while mode.state() == State::Playing {
+        writeln!(output, "{}", mode.prompt())?;
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            mode.give_up();
+            break;
+        }
+
+        let command = if mode.is_guess(&line) { None } else { input::command(&line) };
+        match command {
+            Some(Ok(Line::Quit)) => {
+                mode.give_up();
+                break;
+            }
+            Some(Ok(Line::History)) => mode.history(output)?,
+            Some(Ok(Line::Help)) => {
+                mode.explain(output)?;
+                writeln!(output, "Type {} to guess it, or quit, history or help.", mode.example())?;
+            }
+            Some(_) => writeln!(output, "Hints and saving only work in the classic game.")?,
+            None => mode.guess(&line, output)?,
+        }
+    }
+
+    Ok(())
+}
+
+// What follows an answer while the round goes on with a limit, e.g.
+// " 3 attempts left.".
+fn attempts_left<G, A>(turns: &Turns<G, A>) -> String {
+    match turns.remaining() {
+        Some(1) if turns.is_playing() => " 1 attempt left.".to_string(),
+        Some(n) if turns.is_playing() => format!(" {n} attempts left."),
+        _ => String::new(),
+    }
+}
+
+// The last line of a round: `won` for a win, otherwise what `secret` was,
+// e.g. "word was WISER".
+fn reveal(state: State, won: &str, secret: &str, output: &mut dyn Write) -> io::Result<State> {
+    match state {
+        State::Won => writeln!(output, "{won}")?,
+        State::Lost => writeln!(output, "You lost! The {secret}.")?,
+        _ => writeln!(output, "Giving up? The {secret}.")?,
+    }
+    Ok(state)
+}
+
+impl<D: Domain> Prompted for Round<D> {
+    fn state(&self) -> State {
+        Round::state(self)
+    }
+
+    fn give_up(&mut self) {
+        self.game_mut().give_up();
+    }
+
+    fn prompt(&self) -> String {
+        format!("Please input your guess ({} to {}):", self.first(), self.last())
+    }
+
+    fn example(&self) -> String {
+        format!("a {}", self.domain().noun())
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()> {
+        for (i, (guess, outcome)) in Round::history(self).iter().enumerate() {
+            let answer = match outcome {
+                Outcome::Less => self.domain().too_small(),
+                Outcome::Greater => self.domain().too_big(),
+                Outcome::Won => "Correct!",
+            };
+            writeln!(output, "{:>4}. {guess}: {answer}", i + 1)?;
+        }
+        Ok(())
+    }
+
+    // The letters and words being guessed come before the commands, so a
+    // secret `q` can still be won.
+    fn is_guess(&self, line: &str) -> bool {
+        self.domain().parse(line.trim()).is_ok()
+    }
+
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()> {
+        let outcome = match self.domain().parse(line.trim()).and_then(|guess| Round::guess(self, &guess)) {
+            Ok(outcome) => outcome,
+            Err(reason) => return writeln!(output, "{reason}"),
+        };
+
+        let answer = match outcome {
+            Outcome::Less => self.domain().too_small(),
+            Outcome::Greater => self.domain().too_big(),
+            Outcome::Won => return Ok(()),
+        };
+        writeln!(output, "{answer}{}", attempts_left(self.game().turns()))
+    }
+}
+
+// Plays a round over any domain, reading one guess per line from `input`
+// until the player has won, run out of attempts or input.
+pub fn play_domain<D: Domain, R: BufRead, W: Write>(round: &mut Round<D>, input: R, mut output: W) -> io::Result<State> {
+    let noun = round.domain().noun();
+    writeln!(output, "Guessing the {noun}!")?;
+    prompted(round, input, &mut output)?;
+
+    let secret = format!("secret {noun} was {}", round.secret());
+    reveal(round.state(), "You won!", &secret, &mut output)
+}
+
+// A round of Wordle with the way its marks are shown.
+struct StyledWordle<'a> {
+    game: &'a mut Wordle,
+    style: Style,
+}
+
+impl Prompted for StyledWordle<'_> {
+    fn state(&self) -> State {
+        self.game.turns().state()
+    }
+
+    fn give_up(&mut self) {
+        self.game.give_up();
+    }
+
+    fn example(&self) -> String {
+        "a word".to_string()
+    }
+
+    fn explain(&self, output: &mut dyn Write) -> io::Result<()> {
+        let (correct, present, absent) = match self.style {
+            Style::Color => ("Green", "Yellow", "Grey"),
+            Style::Symbols => ("🟩", "🟨", "⬛"),
+        };
+        writeln!(
+            output,
+            "{correct}: right letter, right place. {present}: right letter, wrong place. {absent}: not in the word."
+        )
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()> {
+        for (guess, marks) in self.game.turns().history() {
+            writeln!(output, "{}", wordle::render(guess, marks, self.style))?;
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()> {
+        let guess = line.trim();
+        match self.game.guess(guess) {
+            Ok(marks) => writeln!(
+                output,
+                "{}{}",
+                wordle::render(guess, &marks, self.style),
+                attempts_left(self.game.turns())
+            ),
+            Err(reason) => writeln!(output, "{reason}"),
+        }
+    }
+}
+
+// Plays a round of Wordle, reading one word per line from `input` until the
+// player has found the word or run out of attempts or input.
+pub fn wordle<R: BufRead, W: Write>(game: &mut Wordle, input: R, mut output: W, style: Style) -> io::Result<State> {
+    let length = game.secret().chars().count();
+    write!(output, "Guess the {length}-letter word!")?;
+    if game.is_hard() {
+        write!(output, " Hard mode: every letter found has to be used.")?;
+    }
+    writeln!(output)?;
+
+    prompted(&mut StyledWordle { game, style }, input, &mut output)?;
+
+    let won = format!("You won in {} attempts!", game.turns().count());
+    let secret = format!("word was {}", game.secret().to_uppercase());
+    reveal(game.turns().state(), &won, &secret, &mut output)
+}
+
+impl Prompted for Mastermind {
+    fn state(&self) -> State {
+        self.turns().state()
+    }
+
+    fn give_up(&mut self) {
+        Mastermind::give_up(self);
+    }
+
+    fn example(&self) -> String {
+        "a code".to_string()
+    }
+
+    fn explain(&self, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(output, "Exact: right symbol in the right place. Partial: right symbol, wrong place.")
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()> {
+        for (guess, feedback) in self.turns().history() {
+            writeln!(output, "{}  {feedback}", self.rules().display(guess))?;
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()> {
+        match self.rules().parse(line.trim()) {
+            Ok(guess) => {
+                let feedback = Mastermind::guess(self, &guess);
+                mastermind_feedback(self, feedback, output)
+            }
+            Err(reason) => writeln!(output, "{reason}"),
+        }
+    }
+}
+
+// Plays a round of code breaking, reading one code per line from `input`
+// until the player has cracked it or run out of attempts or input.
+pub fn mastermind<R: BufRead, W: Write>(game: &mut Mastermind, input: R, mut output: W) -> io::Result<State> {
+    mastermind_start(game, &mut output)?;
+    prompted(game, input, &mut output)?;
+    mastermind_finished(game, &mut output)
+}
+
+// Lets Knuth's solver crack the code, showing how many codes are still
+// possible after every guess.
+pub fn mastermind_solve<W: Write>(game: &mut Mastermind, solver: &mut Knuth, mut output: W) -> io::Result<State> {
+    let rules = *game.rules();
+    mastermind_start(game, &mut output)?;
+
+    while game.turns().is_playing() {
+        let guess = solver.next_guess();
+        write!(output, "{} of {} codes left, guessing ", solver.candidates(), rules.count().unwrap_or(0))?;
+        writeln!(output, "{}", rules.display(&guess))?;
+
+        let feedback = game.guess(&guess);
+        solver.feedback(&guess, feedback);
+        mastermind_feedback(game, feedback, &mut output)?;
+    }
+
+    mastermind_finished(game, &mut output)
+}
+
+fn mastermind_start<W: Write>(game: &Mastermind, mut output: W) -> io::Result<()> {
+    let rules = game.rules();
+    writeln!(
+        output,
+        "Breaking the code: {} symbols out of {}, {}!",
+        rules.length,
+        rules.alphabet_text(),
+        if rules.repeats { "repeats allowed" } else { "no repeats" }
+    )
+}
+
+fn mastermind_feedback(game: &Mastermind, feedback: Feedback, output: &mut dyn Write) -> io::Result<()> {
+    if game.turns().state() == State::Won {
+        return Ok(());
+    }
+    writeln!(output, "{feedback}.{}", attempts_left(game.turns()))
+}
+
+fn mastermind_finished(game: &Mastermind, output: &mut dyn Write) -> io::Result<State> {
+    let won = format!(
+        "Cracked the code {} in {} attempts!",
+        game.rules().display(game.secret()),
+        game.turns().count()
+    );
+    let secret = format!("code was {}", game.rules().display(game.secret()));
+    reveal(game.turns().state(), &won, &secret, output)
+}
+
+impl Prompted for Grid {
+    fn state(&self) -> State {
+        self.turns().state()
+    }
+
+    fn give_up(&mut self) {
+        Grid::give_up(self);
+    }
+
+    fn example(&self) -> String {
+        "a point like 3, 4".to_string()
+    }
+
+    fn explain(&self, output: &mut dyn Write) -> io::Result<()> {
+        match self.clues() {
+            Clues::Directions => writeln!(output, "North and east are larger coordinates, south and west smaller ones."),
+            Clues::Warmth => writeln!(output, "Hotter: closer than the guess before. Colder: further away."),
+        }
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()> {
+        for (guess, answer) in self.turns().history() {
+            writeln!(output, "{guess}  {}", answer.describe())?;
+        }
+
+        // Warmth leaves the whole grid possible, so only directions narrow
+        // it down to a box worth showing.
+        if self.clues() == Clues::Directions && self.turns().is_playing() {
+            let low = Point(self.candidates().iter().map(Interval::low).collect());
+            let high = Point(self.candidates().iter().map(Interval::high).collect());
+            writeln!(output, "The secret is between {low} and {high}.")?;
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()> {
+        let guess = match self.parse(line) {
+            Ok(guess) => guess,
+            Err(reason) => return writeln!(output, "{reason}"),
+        };
+
+        let answer = Grid::guess(self, &guess);
+        if answer == GridAnswer::Found {
+            return Ok(());
+        }
+        writeln!(output, "{}{}", answer.describe(), attempts_left(self.turns()))
+    }
+}
+
+// Plays a round of finding a point on a grid, reading one point per line
+// from `input` until the player has found it or run out of attempts or input.
+pub fn grid<R: BufRead, W: Write>(game: &mut Grid, input: R, mut output: W) -> io::Result<State> {
+    let corner = Point(game.size().axes().iter().map(|axis| *axis.end()).collect());
+    writeln!(
+        output,
+        "Find the secret point on the {} grid, from {} to {corner}!",
+        game.size(),
+        Point(vec![0; game.size().dimensions()])
+    )?;
+
+    prompted(game, input, &mut output)?;
+
+    let won = format!("You found {} in {} attempts!", game.secret(), game.turns().count());
+    let secret = format!("secret point was {}", game.secret());
+    reveal(game.turns().state(), &won, &secret, &mut output)
+}
+
+impl Prompted for NoisyGame {
+    fn state(&self) -> State {
+        self.turns().state()
+    }
+
+    fn give_up(&mut self) {
+        NoisyGame::give_up(self);
+    }
+
+    fn prompt(&self) -> String {
+        format!("Please input your guess ({}-{}):", self.range().start(), self.range().end())
+    }
+
+    fn example(&self) -> String {
+        "a number".to_string()
+    }
+
+    fn explain(&self, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(output, "Some answers are lies, so do not trust any of them too much.")
+    }
+
+    fn history(&self, output: &mut dyn Write) -> io::Result<()> {
+        for (i, (guess, told)) in self.turns().history().iter().enumerate() {
+            writeln!(output, "{:>4}. {guess}: {}", i + 1, noisy_answer(told.outcome))?;
+        }
+        Ok(())
+    }
+
+    fn guess(&mut self, line: &str, output: &mut dyn Write) -> io::Result<()> {
+        match input::parse(line, self.range()) {
+            Ok(Line::Guess(guess)) => {
+                let outcome = NoisyGame::guess(self, guess);
+                noisy_feedback(self, outcome, output)
+            }
+            // Commands never get here.
+            Ok(_) => Ok(()),
+            Err(error) => writeln!(output, "{error}"),
+        }
+    }
+}
+
+// Plays a round against a host that sometimes lies, reading one guess per
+// line from `input` until the player has won or run out of attempts or input.
+pub fn noisy<R: BufRead, W: Write>(game: &mut NoisyGame, input: R, mut output: W) -> io::Result<State> {
+    noisy_start(game, &mut output)?;
+    prompted(game, input, &mut output)?;
+    noisy_finished(game, &mut output)
+}
+
+// Lets the solver find the number in spite of the lies, showing how sure it
+// is of every guess, and compares the guesses it needed with the bound.
+pub fn noisy_solve<W: Write>(game: &mut NoisyGame, solver: &mut Ulam, mut output: W) -> io::Result<State> {
+    noisy_start(game, &mut output)?;
+
+    while game.turns().is_playing() {
+        let (guess, confidence) = solver.next_guess();
+        match solver.candidates() {
+            1 => write!(output, "1 candidate left")?,
+            n => write!(output, "{n} candidates left")?,
+        }
+        writeln!(output, ", guessing {guess}, {:.1}% sure", confidence * 100.0)?;
+
+        let outcome = game.guess(guess);
+        solver.answer(guess, outcome);
+        noisy_feedback(game, outcome, &mut output)?;
+    }
+
+    let state = noisy_finished(game, &mut output)?;
+    let range = game.range();
+    let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+    let used = game.turns().count();
+    // This host lies at random and never about a right guess, so it is far
+    // kinder than the one the bounds are for.
+    match (game.noise(), liar::query_bound(size, game.noise())) {
+        (noise @ Noise::Limit(_), Some(bound)) => writeln!(
+            output,
+            "Used {used} guesses. In the worst case, when the host {noise} and picks the moments to do so, {bound} yes-or-no questions are needed for {size} numbers."
+        )?,
+        (noise, Some(bound)) => writeln!(
+            output,
+            "Used {used} guesses. When the host {noise}, about {bound} yes-or-no questions are needed for {size} numbers."
+        )?,
+        (_, None) => writeln!(output, "Used {used} guesses. In theory, no number of questions is enough.")?,
+    }
+
+    Ok(state)
+}
+
+fn noisy_start<W: Write>(game: &NoisyGame, mut output: W) -> io::Result<()> {
+    let range = game.range();
+    writeln!(
+        output,
+        "Guess the number from {} to {}! Careful, the host {}.",
+        range.start(),
+        range.end(),
+        game.noise()
+    )
+}
+
+fn noisy_answer(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Less => "Too small guess!",
+        Outcome::Greater => "Too big guess!",
+        Outcome::Won => "Correct!",
+    }
+}
+
+fn noisy_feedback(game: &NoisyGame, outcome: Outcome, output: &mut dyn Write) -> io::Result<()> {
+    if outcome == Outcome::Won {
+        return Ok(());
+    }
+    writeln!(output, "{}{}", noisy_answer(outcome), attempts_left(game.turns()))
+}
+
+fn noisy_finished(game: &NoisyGame, output: &mut dyn Write) -> io::Result<State> {
+    let won = format!("You won in {} attempts!", game.turns().count());
+    let secret = format!("secret number was {}", game.secret_number());
+    let state = reveal(game.turns().state(), &won, &secret, output)?;
+
+    let lied_about: Vec<String> = game.lied_about().iter().map(u32::to_string).collect();
+    match lied_about.len() {
+        0 => writeln!(output, "The host told no lies.")?,
+        1 => writeln!(output, "The host lied once, about {}.", lied_about[0])?,
+        n => writeln!(output, "The host lied {n} times, about {}.", lied_about.join(", "))?,
+    }
+    Ok(state)
+}
+
+// Lets `strategy` play the round on its own and reports every guess it
+// makes, until it has won or run out of attempts. Without a limit it gives
+// up after `MAX_GUESSES`.
+pub fn autoplay(game: &mut Game, strategy: &mut dyn Strategy, reporter: &mut dyn Reporter) -> io::Result<State> {
+    reporter.start(game)?;
+
+    while game.state() == State::Playing {
+        if game.attempts() >= MAX_GUESSES {
+            game.give_up();
+            break;
+        }
+        reporter.prompt(game)?;
+
+        let guess = strategy.next_guess();
+        reporter.echo(guess)?;
+
+        let outcome = game.guess(guess);
+        reporter.guessed(game, guess, outcome)?;
+        strategy.feedback(guess, outcome.ordering());
+    }
+
+    reporter.finished(game)?;
+
+    Ok(game.state())
+}
+
+// The reverse game: the player thinks of a number in `range` and answers the
+// computer's guesses from `input`, until it has found the number.
+pub fn reverse<R: BufRead, W: Write>(range: RangeInclusive<u32>, mut input: R, mut output: W) -> io::Result<State> {
+    let mut game = Reverse::new(range);
+    writeln!(
+        output,
+        "Think of a number between {} and {}, and I will guess it!",
+        game.range().start(),
+        game.range().end()
+    )?;
+
+    let mut guess = game.next_guess();
+    let mut attempts = 1;
+
+    loop {
+        writeln!(output, "Is it {guess}? (higher/lower/correct)")?;
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output, "Giving up? I was so close!")?;
+            return Ok(State::GaveUp);
+        }
+
+        let answer: Answer = match line.parse() {
+            Ok(answer) => answer,
+            Err(_) => {
+                writeln!(output, "Please answer higher, lower or correct (h/l/c).")?;
+                continue;
+            }
+        };
+
+        match game.answer(guess, answer) {
+            Ok(()) if answer == Answer::Correct => {
+                writeln!(output, "I guessed your number {guess} in {attempts} attempts!")?;
+                return Ok(State::Won);
+            }
+            Ok(()) => {
+                guess = game.next_guess();
+                attempts += 1;
+            }
+            Err(Contradiction::Range) => {
+                writeln!(
+                    output,
+                    "That can not be right: '{answer}' for {guess} means your number is not between {} and {}.",
+                    game.range().start(),
+                    game.range().end()
+                )?;
+            }
+            Err(Contradiction::Answer(index)) => {
+                let (earlier_guess, earlier_answer) = game.answers()[index];
+                writeln!(
+                    output,
+                    "That can not be right: '{answer}' for {guess} contradicts your answer '{earlier_answer}' for {earlier_guess} (guess #{}).",
+                    index + 1
+                )?;
+            }
+        }
+    }
+}
+
+// Asks for the names of `count` players, one per line. Players who do not
+// give a name are called "Player 1", "Player 2" and so on.
+pub fn ask_names<R: BufRead, W: Write>(count: usize, mut input: R, mut output: W) -> io::Result<Vec<String>> {
+    let mut names = Vec::with_capacity(count);
+
+    for number in 1..=count {
+        writeln!(output, "Name of player {number}:")?;
+        let mut name = String::new();
+        input.read_line(&mut name)?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            names.push(format!("Player {number}"));
+        } else {
+            names.push(name.to_string());
+        }
+    }
+
+    Ok(names)
+}
+
+// Plays `contest` by reading the guess of whoever's turn it is from `input`,
+// until someone has won or nobody can guess any more.
+pub fn contest<R: BufRead, W: Write>(contest: &mut Contest, mut input: R, mut output: W) -> io::Result<Standing> {
+    match contest.mode() {
+        Mode::HotSeat => writeln!(output, "Guessing the number, everyone guesses the same one!")?,
+        Mode::Race => writeln!(output, "Guessing the number, everyone has a number of their own!")?,
+    }
+    let names: Vec<&str> = contest.players().iter().map(|player| player.name.as_str()).collect();
+    writeln!(output, "Turn order: {}", names.join(", "))?;
+
+    while contest.standing().is_none() {
+        let player = contest.turn();
+        let name = contest.players()[player].name.clone();
+        let range = contest.game(player).range().clone();
+
+        writeln!(
+            output,
+            "{name}, please input your guess ({}-{}):",
+            range.start(),
+            range.end()
+        )?;
+        let mut guess = String::new();
+
+        if input.read_line(&mut guess)? == 0 {
+            contest.give_up();
+            break;
+        }
+
+        let guess = match input::parse(&guess, &range) {
+            Ok(Line::Guess(guess)) => guess,
+            Ok(Line::Quit) => {
+                contest.give_up();
+                break;
+            }
+            Ok(_) => {
+                writeln!(output, "Only guesses and 'quit' work when playing together.")?;
+                continue;
+            }
+            Err(error) => {
+                writeln!(output, "{error}")?;
+                continue;
+            }
+        };
+
+        match contest.guess(guess) {
+            Outcome::Less => write!(output, "{name}: Too small guess!")?,
+            Outcome::Greater => write!(output, "{name}: Too big guess!")?,
+            Outcome::Won => write!(output, "{name}: Correct!")?,
+        }
+        match contest.attempts_remaining(player) {
+            Some(1) => writeln!(output, " 1 attempt left.")?,
+            Some(n) => writeln!(output, " {n} attempts left.")?,
+            None => writeln!(output)?,
+        }
+    }
+
+    writeln!(output, "Attempts:")?;
+    for player in contest.players() {
+        writeln!(output, "  {}: {}", player.name, player.attempts)?;
+    }
+
+    let standing = contest.standing().cloned().unwrap_or(Standing::NoWinner);
+    match &standing {
+        Standing::Winner(player) => {
+            writeln!(output, "{} won!", contest.players()[*player].name)?;
+        }
+        Standing::Tie(players) => {
+            let names: Vec<&str> = players
+                .iter()
+                .map(|&player| contest.players()[player].name.as_str())
+                .collect();
+            writeln!(output, "It is a tie between {}!", names.join(" and "))?;
+        }
+        Standing::NoWinner => match contest.mode() {
+            Mode::HotSeat => writeln!(
+                output,
+                "Nobody won! The secret number was {}.",
+                contest.game(0).secret_number()
+            )?,
+            Mode::Race => writeln!(output, "Nobody won!")?,
+        },
+    }
+
+    Ok(standing)
+}
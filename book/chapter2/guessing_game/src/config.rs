@@ -0,0 +1,480 @@
+use std::env;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::domain::Kind;
+use crate::game::fair_attempts;
+use crate::grid::Size;
+use crate::liar::MAX_LIES;
+use crate::scores::ScoreFile;
+use crate::strategy::StrategyKind;
+
+// Named ranges for the secret number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=10_000,
+            Difficulty::Insane => 1..=u32::MAX,
+        }
+    }
+
+    // The preset with exactly this range, if there is one.
+    pub fn for_range(range: &RangeInclusive<u32>) -> Option<Difficulty> {
+        [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Insane]
+            .into_iter()
+            .find(|difficulty| difficulty.range() == *range)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "insane" => Ok(Difficulty::Insane),
+            _ => Err(format!(
+                "unknown difficulty '{s}', expected easy, normal, hard or insane"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// How the game talks back: chatty text or a full-screen view for people,
+// `key=value` lines or JSON lines for programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Tui,
+    Plain,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "human" => Ok(Format::Human),
+            "tui" => Ok(Format::Tui),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{s}', expected human, tui, plain or json")),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Format::Human => "human",
+            Format::Tui => "tui",
+            Format::Plain => "plain",
+            Format::Json => "json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// What the program was asked to do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    Simulate,
+    Scores,
+    Serve,
+    Connect,
+    Api,
+    Replay,
+}
+
+// Options given on the command line.
+// The modes that play a round of their own instead of guessing numbers. Only
+// one can be picked at a time.
+const MODES: [&str; 7] = ["--domain", "--wordle", "--mastermind", "--grid", "--lies", "--noise", "--reverse"];
+
+// What the modes of their own leave out: they play a single round for a
+// person, as text, without saving or a clock.
+const SINGLE_ROUND: [&str; 7] = [
+    "--autoplay",
+    "--players",
+    "--resume",
+    "--save-file",
+    "--format",
+    "--time-limit",
+    "--speedrun",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub command: Command,
+    pub seed: Option<u64>,
+    pub difficulty: Difficulty,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub max_attempts: Option<u32>,
+    pub fair: bool,
+    pub evil: bool,
+    pub reverse: bool,
+    pub script: Option<PathBuf>,
+    pub format: Option<Format>,
+    pub autoplay: Option<StrategyKind>,
+    pub games: Option<u32>,
+    pub strategies: Vec<StrategyKind>,
+    pub csv: bool,
+    pub name: Option<String>,
+    pub scores: Option<PathBuf>,
+    pub top: Option<usize>,
+    pub time_limit: Option<u64>,
+    pub speedrun: bool,
+    pub players: Vec<String>,
+    pub player_count: Option<usize>,
+    pub race: bool,
+    pub port: Option<u16>,
+    pub idle_timeout: Option<u64>,
+    pub address: Option<String>,
+    pub expiry: Option<u64>,
+    pub resume: Option<PathBuf>,
+    pub save_file: Option<PathBuf>,
+    pub obfuscate: bool,
+    pub record: Option<PathBuf>,
+    pub transcript: Option<PathBuf>,
+    pub speed: Option<u32>,
+    pub verify: bool,
+    pub domain: Kind,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub epsilon: Option<f64>,
+    pub words: Option<PathBuf>,
+    pub wordle: bool,
+    pub hard: bool,
+    pub symbols: bool,
+    pub mastermind: bool,
+    pub length: Option<usize>,
+    pub alphabet: Option<u8>,
+    pub no_repeats: bool,
+    pub colors: bool,
+    pub solve: bool,
+    pub grid: Option<Size>,
+    pub warmth: bool,
+    pub lies: Option<u32>,
+    pub noise: Option<f64>,
+}
+
+impl Config {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Config, String> {
+        // The first argument is the name of the program.
+        let mut args = args.skip(1).peekable();
+
+        let mut config = Config::default();
+
+        match args.peek().map(String::as_str) {
+            Some("simulate") => config.command = Command::Simulate,
+            Some("scores") => config.command = Command::Scores,
+            Some("serve") => config.command = Command::Serve,
+            Some("connect") => config.command = Command::Connect,
+            Some("api") => config.command = Command::Api,
+            Some("replay") => config.command = Command::Replay,
+            _ => {}
+        }
+        if config.command != Command::Play {
+            args.next();
+        }
+
+        if config.command == Command::Connect {
+            config.address = args.next_if(|arg| !arg.starts_with("--"));
+        }
+
+        if config.command == Command::Replay {
+            config.transcript = args.next_if(|arg| !arg.starts_with("--")).map(PathBuf::from);
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => config.seed = Some(parse_value(&arg, args.next())?),
+                "--difficulty" => config.difficulty = parse_value(&arg, args.next())?,
+                "--min" => config.min = Some(parse_value(&arg, args.next())?),
+                "--max" => config.max = Some(parse_value(&arg, args.next())?),
+                "--attempts" => config.max_attempts = Some(parse_value(&arg, args.next())?),
+                "--fair" => config.fair = true,
+                "--evil" => config.evil = true,
+                "--reverse" => config.reverse = true,
+                "--script" => config.script = Some(parse_value(&arg, args.next())?),
+                "--format" => config.format = Some(parse_value(&arg, args.next())?),
+                "--autoplay" => config.autoplay = Some(parse_value(&arg, args.next())?),
+                "--games" => config.games = Some(parse_value(&arg, args.next())?),
+                "--strategies" => config.strategies = parse_list(&arg, args.next())?,
+                "--csv" => config.csv = true,
+                "--name" => config.name = Some(parse_value(&arg, args.next())?),
+                "--scores" => config.scores = Some(parse_value(&arg, args.next())?),
+                "--top" => config.top = Some(parse_value(&arg, args.next())?),
+                "--time-limit" => config.time_limit = Some(parse_value(&arg, args.next())?),
+                "--speedrun" => config.speedrun = true,
+                "--players" => {
+                    // Either the names of the players, or how many players to
+                    // ask the names of.
+                    let value: String = parse_value(&arg, args.next())?;
+                    match value.parse() {
+                        Ok(count) => config.player_count = Some(count),
+                        Err(_) => config.players = parse_list(&arg, Some(value))?,
+                    }
+                }
+                "--race" => config.race = true,
+                "--port" => config.port = Some(parse_value(&arg, args.next())?),
+                "--idle-timeout" => config.idle_timeout = Some(parse_value(&arg, args.next())?),
+                "--expiry" => config.expiry = Some(parse_value(&arg, args.next())?),
+                "--resume" => config.resume = Some(parse_value(&arg, args.next())?),
+                "--save-file" => config.save_file = Some(parse_value(&arg, args.next())?),
+                "--obfuscate" => config.obfuscate = true,
+                "--record" => config.record = Some(parse_value(&arg, args.next())?),
+                "--speed" => config.speed = Some(parse_value(&arg, args.next())?),
+                "--verify" => config.verify = true,
+                "--domain" => config.domain = parse_value(&arg, args.next())?,
+                "--from" => config.from = Some(parse_value(&arg, args.next())?),
+                "--to" => config.to = Some(parse_value(&arg, args.next())?),
+                "--epsilon" => config.epsilon = Some(parse_value(&arg, args.next())?),
+                "--words" => config.words = Some(parse_value(&arg, args.next())?),
+                "--wordle" => config.wordle = true,
+                "--hard" => config.hard = true,
+                "--symbols" => config.symbols = true,
+                "--mastermind" => config.mastermind = true,
+                "--length" => config.length = Some(parse_value(&arg, args.next())?),
+                "--alphabet" => config.alphabet = Some(parse_value(&arg, args.next())?),
+                "--no-repeats" => config.no_repeats = true,
+                "--colors" => config.colors = true,
+                "--solve" => config.solve = true,
+                "--grid" => config.grid = Some(parse_value(&arg, args.next())?),
+                "--warmth" => config.warmth = true,
+                "--lies" => config.lies = Some(parse_value(&arg, args.next())?),
+                "--noise" => config.noise = Some(parse_value(&arg, args.next())?),
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        let range = config.range();
+        if range.start() > range.end() {
+            return Err(format!(
+                "the range {}-{} is empty, --min must not be greater than --max",
+                range.start(),
+                range.end()
+            ));
+        }
+
+        if config.max_attempts == Some(0) {
+            return Err("--attempts must be at least 1".to_string());
+        }
+
+        if config.fair && config.max_attempts.is_some() {
+            return Err("--fair and --attempts can not be used together".to_string());
+        }
+
+        if config.time_limit == Some(0) {
+            return Err("--time-limit must be at least 1 second".to_string());
+        }
+
+        if config.player_count.map_or(config.players.len() == 1, |count| count < 2) {
+            return Err("--players needs at least two players".to_string());
+        }
+
+        if config.race && config.player_count.is_none() && config.players.is_empty() {
+            return Err("--race needs --players".to_string());
+        }
+
+        if config.idle_timeout == Some(0) {
+            return Err("--idle-timeout must be at least 1 second".to_string());
+        }
+
+        if config.games == Some(0) {
+            return Err("--games must be at least 1".to_string());
+        }
+
+        if config.domain == Kind::Number && (config.from.is_some() || config.to.is_some()) {
+            return Err("--from and --to need --domain, use --min and --max for numbers".to_string());
+        }
+
+        if config.epsilon.is_some() && config.domain != Kind::Float {
+            return Err("--epsilon only works with --domain float".to_string());
+        }
+
+        if config.words.is_some() && config.domain != Kind::Word && !config.wordle {
+            return Err("--words only works with --domain word or --wordle".to_string());
+        }
+
+        if (config.hard || config.symbols) && !config.wordle {
+            return Err("--hard and --symbols only work with --wordle".to_string());
+        }
+
+        let code_options = config.length.is_some() || config.alphabet.is_some() || config.no_repeats || config.colors;
+        if code_options && !config.mastermind {
+            return Err("--length, --alphabet, --no-repeats and --colors only work with --mastermind".to_string());
+        }
+
+        let noisy = config.lies.is_some() || config.noise.is_some();
+        if config.solve && !config.mastermind && !noisy {
+            return Err("--solve only works with --mastermind, --lies or --noise".to_string());
+        }
+
+        if config.warmth && config.grid.is_none() {
+            return Err("--warmth only works with --grid".to_string());
+        }
+
+        if config.warmth && config.fair {
+            return Err("--fair needs the directions, it does not work with --warmth".to_string());
+        }
+
+        if config.lies.is_some() && config.noise.is_some() {
+            return Err("--lies and --noise cannot be combined, the host lies either up to a number of times or by chance".to_string());
+        }
+
+        if config.lies.is_some_and(|k| k > MAX_LIES) {
+            return Err(format!("--lies can be at most {MAX_LIES}"));
+        }
+
+        if config.noise.is_some_and(|p| !(0.0..0.5).contains(&p)) {
+            return Err("--noise must be at least 0 and less than 0.5, or the answers tell nothing".to_string());
+        }
+
+        config.check_mode("--domain", &[])?;
+        config.check_mode("--wordle", &["--evil", "--fair"])?;
+        config.check_mode("--mastermind", &["--evil", "--fair"])?;
+        config.check_mode("--grid", &["--evil"])?;
+        config.check_mode("--lies", &["--evil", "--fair"])?;
+        config.check_mode("--noise", &["--evil", "--fair"])?;
+        config.check_mode("--reverse", &["--evil", "--fair", "--attempts"])?;
+
+        if config.command == Command::Replay && config.transcript.is_none() {
+            return Err("replay needs the transcript to play back".to_string());
+        }
+
+        Ok(config)
+    }
+
+    // The mode of its own the round is played in, if any, like `--wordle`.
+    pub fn mode(&self) -> Option<&'static str> {
+        MODES.into_iter().find(|&mode| self.is_set(mode))
+    }
+
+    // Whether `flag` was given, or for `--format` a format other than the
+    // default text, which is all the modes of their own print.
+    fn is_set(&self, flag: &str) -> bool {
+        match flag {
+            "--domain" => self.domain != Kind::Number,
+            "--wordle" => self.wordle,
+            "--mastermind" => self.mastermind,
+            "--grid" => self.grid.is_some(),
+            "--lies" => self.lies.is_some(),
+            "--noise" => self.noise.is_some(),
+            "--reverse" => self.reverse,
+            "--autoplay" => self.autoplay.is_some(),
+            "--players" => self.player_count.is_some() || !self.players.is_empty(),
+            "--resume" => self.resume.is_some(),
+            "--save-file" => self.save_file.is_some(),
+            "--format" => self.format.is_some_and(|format| format != Format::Human),
+            "--time-limit" => self.time_limit.is_some(),
+            "--speedrun" => self.speedrun,
+            "--evil" => self.evil,
+            "--fair" => self.fair,
+            "--attempts" => self.max_attempts.is_some(),
+            _ => false,
+        }
+    }
+
+    // If `mode` is set, rejects any other mode along with it, the flags in
+    // `SINGLE_ROUND` and those in `unsupported`.
+    fn check_mode(&self, mode: &str, unsupported: &[&str]) -> Result<(), String> {
+        if !self.is_set(mode) {
+            return Ok(());
+        }
+
+        let flags = MODES.iter().chain(&SINGLE_ROUND).chain(unsupported);
+        match flags.filter(|&&flag| flag != mode).find(|&&flag| self.is_set(flag)) {
+            Some(flag) => Err(format!("{mode} does not work with {flag}")),
+            None => Ok(()),
+        }
+    }
+
+    // The high-score file given with --scores, or the default one.
+    pub fn score_file(&self) -> Option<ScoreFile> {
+        self.scores
+            .clone()
+            .or_else(ScoreFile::default_path)
+            .map(ScoreFile::new)
+    }
+
+    // The name given with --name, or the name of the logged in user.
+    pub fn player_name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| env::var("USER").ok())
+            .unwrap_or_else(|| "anonymous".to_string())
+    }
+
+    // The difficulty preset, with any explicit --min or --max applied on top.
+    pub fn range(&self) -> RangeInclusive<u32> {
+        let preset = self.difficulty.range();
+        let min = self.min.unwrap_or(*preset.start());
+        let max = self.max.unwrap_or(*preset.end());
+        min..=max
+    }
+
+    // The attempt limit asked for, if any. In fair mode it is just enough
+    // for a perfect player to always find the number.
+    pub fn max_attempts(&self) -> Option<u32> {
+        if self.fair {
+            Some(fair_attempts(&self.range()))
+        } else {
+            self.max_attempts
+        }
+    }
+}
+
+fn parse_value<T>(flag: &str, value: Option<String>) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = value.ok_or(format!("{flag} needs a value"))?;
+    value
+        .parse()
+        .map_err(|e| format!("invalid value '{value}' for {flag}: {e}"))
+}
+
+fn parse_list<T>(flag: &str, value: Option<String>) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = value.ok_or(format!("{flag} needs a value"))?;
+    value
+        .split(',')
+        .map(|item| parse_value(flag, Some(item.trim().to_string())))
+        .collect()
+}
@@ -0,0 +1,167 @@
+use crate::game::{Game, Outcome, State};
+
+// How several players share a contest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // Everyone takes turns guessing the same secret number, and whoever
+    // finds it first wins.
+    HotSeat,
+    // Everyone has a secret number of their own. Whoever finds theirs in the
+    // fewest turns wins; finding it in the same turn is a tie.
+    Race,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Standing {
+    Winner(usize),
+    Tie(Vec<usize>),
+    NoWinner,
+}
+
+// Two or more players taking turns, one guess each.
+#[derive(Debug, Clone)]
+pub struct Contest {
+    mode: Mode,
+    players: Vec<Player>,
+    // One game shared by everyone in hot-seat mode, one per player in a race.
+    games: Vec<Game>,
+    max_attempts: Option<u32>,
+    turn: usize,
+    standing: Option<Standing>,
+}
+
+impl Contest {
+    // `max_attempts` limits the guesses of every player, not of the game.
+    pub fn hot_seat(names: Vec<String>, game: Game, max_attempts: Option<u32>) -> Contest {
+        Contest::new(Mode::HotSeat, names, vec![game], max_attempts)
+    }
+
+    // Player `i` plays `games[i]`, which may have an attempt limit of its own.
+    pub fn race(names: Vec<String>, games: Vec<Game>) -> Contest {
+        assert_eq!(names.len(), games.len(), "every player needs a game");
+        Contest::new(Mode::Race, names, games, None)
+    }
+
+    fn new(mode: Mode, names: Vec<String>, games: Vec<Game>, max_attempts: Option<u32>) -> Contest {
+        assert!(names.len() >= 2, "a contest needs at least two players");
+
+        let players = names
+            .into_iter()
+            .map(|name| Player { name, attempts: 0 })
+            .collect();
+
+        Contest {
+            mode,
+            players,
+            games,
+            max_attempts,
+            turn: 0,
+            standing: None,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    // Whose turn it is.
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    // The game `player` is guessing in.
+    pub fn game(&self, player: usize) -> &Game {
+        match self.mode {
+            Mode::HotSeat => &self.games[0],
+            Mode::Race => &self.games[player],
+        }
+    }
+
+    pub fn attempts_remaining(&self, player: usize) -> Option<u32> {
+        match self.mode {
+            Mode::HotSeat => self
+                .max_attempts
+                .map(|max| max.saturating_sub(self.players[player].attempts)),
+            Mode::Race => self.games[player].attempts_remaining(),
+        }
+    }
+
+    // The outcome, once the contest is over.
+    pub fn standing(&self) -> Option<&Standing> {
+        self.standing.as_ref()
+    }
+
+    // Makes `guess` for the player whose turn it is and passes the turn on.
+    pub fn guess(&mut self, guess: u32) -> Outcome {
+        let player = self.turn;
+        self.players[player].attempts += 1;
+
+        let game = match self.mode {
+            Mode::HotSeat => &mut self.games[0],
+            Mode::Race => &mut self.games[player],
+        };
+        let outcome = game.guess(guess);
+
+        if self.mode == Mode::HotSeat && outcome == Outcome::Won {
+            self.standing = Some(Standing::Winner(player));
+            return outcome;
+        }
+
+        match self.next_player(player) {
+            // In a race everyone gets as many turns as the winner did, so the
+            // contest is only decided at the end of a round.
+            Some(next) if next > player || self.winners().is_empty() => self.turn = next,
+            _ => self.standing = Some(self.final_standing()),
+        }
+
+        outcome
+    }
+
+    // Ends the contest early, e.g. when the players run out of input.
+    pub fn give_up(&mut self) {
+        if self.standing.is_none() {
+            self.standing = Some(self.final_standing());
+        }
+    }
+
+    fn can_play(&self, player: usize) -> bool {
+        self.game(player).state() == State::Playing
+            && self.attempts_remaining(player) != Some(0)
+    }
+
+    // The next player after `player` in turn order who can still guess.
+    fn next_player(&self, player: usize) -> Option<usize> {
+        let count = self.players.len();
+        (1..=count)
+            .map(|step| (player + step) % count)
+            .find(|&next| self.can_play(next))
+    }
+
+    fn winners(&self) -> Vec<usize> {
+        match self.mode {
+            Mode::HotSeat => Vec::new(),
+            Mode::Race => (0..self.players.len())
+                .filter(|&player| self.games[player].state() == State::Won)
+                .collect(),
+        }
+    }
+
+    fn final_standing(&self) -> Standing {
+        let mut winners = self.winners();
+        match winners.len() {
+            0 => Standing::NoWinner,
+            1 => Standing::Winner(winners.remove(0)),
+            _ => Standing::Tie(winners),
+        }
+    }
+}
@@ -0,0 +1,532 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use rand::RngCore;
+
+use crate::game::{Game, Outcome, State};
+use crate::calendar::{civil_from_days, days_from_civil};
+
+// The words that come with the game, one per line.
+pub const WORDS: &str = include_str!("words.txt");
+
+// What the secret can be, picked with --domain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Kind {
+    // The classic game with whole numbers from --min to --max.
+    #[default]
+    Number,
+    Integer,
+    Float,
+    Letter,
+    Word,
+    Date,
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Kind, String> {
+        match s {
+            "number" => Ok(Kind::Number),
+            "int" => Ok(Kind::Integer),
+            "float" => Ok(Kind::Float),
+            "letter" => Ok(Kind::Letter),
+            "word" => Ok(Kind::Word),
+            "date" => Ok(Kind::Date),
+            _ => Err(format!(
+                "unknown domain '{s}', expected number, int, float, letter, word or date"
+            )),
+        }
+    }
+}
+
+// A finite, ordered set of values the secret is drawn from. The game itself
+// only ever sees where a value is in the domain, counting from 0, so any
+// `T: Ord` works as long as its values can be numbered in order.
+pub trait Domain {
+    type Value: Ord + Clone + fmt::Display;
+
+    // How many values there are, at most 2^32.
+    fn size(&self) -> u64;
+
+    // The value at `index`, which is less than `size()`.
+    fn value(&self, index: u32) -> Self::Value;
+
+    // Where `value` is in the domain, or `None` if it is outside of it.
+    fn index(&self, value: &Self::Value) -> Option<u32>;
+
+    fn parse(&self, text: &str) -> Result<Self::Value, String>;
+
+    // What a single value is called, e.g. "letter".
+    fn noun(&self) -> &'static str;
+
+    // The answers to a guess that comes before or after the secret.
+    fn too_small(&self) -> &'static str {
+        "Too small guess!"
+    }
+
+    fn too_big(&self) -> &'static str {
+        "Too big guess!"
+    }
+}
+
+// A round played over the values of a domain instead of plain numbers. The
+// work is done by a `Game` over the positions of the values.
+#[derive(Debug, Clone)]
+pub struct Round<D: Domain> {
+    domain: D,
+    game: Game,
+}
+
+impl<D: Domain> Round<D> {
+    // Panics if `secret` is not in `domain`.
+    pub fn new(domain: D, secret: &D::Value) -> Round<D> {
+        let index = domain.index(secret).expect("the secret is in the domain");
+        let game = Game::new(0..=last_index(&domain), index);
+        Round { domain, game }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(domain: D, rng: &mut R) -> Round<D> {
+        let game = Game::from_rng(0..=last_index(&domain), rng);
+        Round { domain, game }
+    }
+
+    pub fn evil(domain: D) -> Round<D> {
+        let game = Game::evil(0..=last_index(&domain));
+        Round { domain, game }
+    }
+
+    pub fn with_max_attempts(self, max_attempts: u32) -> Round<D> {
+        Round {
+            game: self.game.with_max_attempts(max_attempts),
+            ..self
+        }
+    }
+
+    // Fails without counting the guess if it is outside of the domain.
+    pub fn guess(&mut self, value: &D::Value) -> Result<Outcome, String> {
+        match self.domain.index(value) {
+            Some(index) => Ok(self.game.guess(index)),
+            None => Err(format!(
+                "{value} is not between {} and {}.",
+                self.first(),
+                self.last()
+            )),
+        }
+    }
+
+    pub fn domain(&self) -> &D {
+        &self.domain
+    }
+
+    // The game over the positions of the values, for anything that does not
+    // care what the values are.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    pub fn first(&self) -> D::Value {
+        self.domain.value(0)
+    }
+
+    pub fn last(&self) -> D::Value {
+        self.domain.value(last_index(&self.domain))
+    }
+
+    pub fn secret(&self) -> D::Value {
+        self.domain.value(self.game.secret_number())
+    }
+
+    // Every guess so far, as the value it was snapped to, with its answer.
+    pub fn history(&self) -> Vec<(D::Value, Outcome)> {
+        self.game
+            .history()
+            .iter()
+            .map(|&(index, outcome)| (self.domain.value(index), outcome))
+            .collect()
+    }
+
+    pub fn state(&self) -> State {
+        self.game.state()
+    }
+}
+
+fn last_index<D: Domain>(domain: &D) -> u32 {
+    (domain.size() - 1) as u32
+}
+
+// Checks that a domain has at least one and at most 2^32 values. The size is
+// an `i128` because the widest ranges of `i64` have 2^64 values.
+fn check_size(size: i128) -> Result<(), String> {
+    match size {
+        0 => Err("the domain is empty".to_string()),
+        size if size > 1 << 32 => Err(format!("the domain has {size} values, at most 2^32 are possible")),
+        _ => Ok(()),
+    }
+}
+
+// Whole numbers that may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Integers {
+    min: i64,
+    max: i64,
+}
+
+impl Integers {
+    pub fn new(min: i64, max: i64) -> Result<Integers, String> {
+        if min > max {
+            return Err(format!("the range {min} to {max} is empty"));
+        }
+        check_size(i128::from(max) - i128::from(min) + 1)?;
+        Ok(Integers { min, max })
+    }
+}
+
+impl Domain for Integers {
+    type Value = i64;
+
+    fn size(&self) -> u64 {
+        (self.max - self.min) as u64 + 1
+    }
+
+    fn value(&self, index: u32) -> i64 {
+        self.min + i64::from(index)
+    }
+
+    fn index(&self, value: &i64) -> Option<u32> {
+        (self.min..=self.max)
+            .contains(value)
+            .then(|| (value - self.min) as u32)
+    }
+
+    fn parse(&self, text: &str) -> Result<i64, String> {
+        text.replace('_', "")
+            .parse()
+            .map_err(|_| format!("'{text}' is not a whole number."))
+    }
+
+    fn noun(&self) -> &'static str {
+        "number"
+    }
+}
+
+// A floating-point number that can be ordered. NaN never gets this far, as
+// the parser turns it away.
+#[derive(Debug, Clone, Copy)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Float) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Float) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// Numbers from `min` to `max` in steps of `epsilon`. A guess counts as the
+// step it is closest to, so anything within half a step of the secret wins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Floats {
+    min: f64,
+    max: f64,
+    epsilon: f64,
+    // How many decimals `epsilon` has, to round the values to.
+    decimals: i32,
+}
+
+impl Floats {
+    pub fn new(min: f64, max: f64, epsilon: f64) -> Result<Floats, String> {
+        if !(min.is_finite() && max.is_finite()) || min > max {
+            return Err(format!("the range {min} to {max} is empty"));
+        }
+        if !(epsilon.is_finite() && epsilon > 0.0) {
+            return Err(format!("the epsilon {epsilon} is not a positive number"));
+        }
+        // Leave some room for rounding errors when `max - min` is a whole
+        // number of steps.
+        let steps = ((max - min) / epsilon + 1e-9).floor();
+        // Casting saturates, which is plenty to tell that it is too many.
+        check_size((steps + 1.0) as i128)?;
+
+        let decimals = (-epsilon.log10().floor() as i32).max(0) + 1;
+        Ok(Floats {
+            min,
+            max,
+            epsilon,
+            decimals,
+        })
+    }
+}
+
+impl Domain for Floats {
+    type Value = Float;
+
+    fn size(&self) -> u64 {
+        ((self.max - self.min) / self.epsilon + 1e-9).floor() as u64 + 1
+    }
+
+    fn value(&self, index: u32) -> Float {
+        let scale = 10f64.powi(self.decimals);
+        let value = self.min + f64::from(index) * self.epsilon;
+        Float((value * scale).round() / scale)
+    }
+
+    fn index(&self, value: &Float) -> Option<u32> {
+        let index = ((value.0 - self.min) / self.epsilon).round();
+        (index >= 0.0 && (index as u64) < self.size()).then_some(index as u32)
+    }
+
+    fn parse(&self, text: &str) -> Result<Float, String> {
+        match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Float(value)),
+            _ => Err(format!("'{text}' is not a number.")),
+        }
+    }
+
+    fn noun(&self) -> &'static str {
+        "number"
+    }
+}
+
+// Letters of the alphabet, without regard to case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Letters {
+    first: char,
+    last: char,
+}
+
+impl Letters {
+    pub fn new(first: char, last: char) -> Result<Letters, String> {
+        let (first, last) = (first.to_ascii_lowercase(), last.to_ascii_lowercase());
+        if !(first.is_ascii_lowercase() && last.is_ascii_lowercase()) {
+            return Err("only the letters a to z can be used".to_string());
+        }
+        if first > last {
+            return Err(format!("the range {first} to {last} is empty"));
+        }
+        Ok(Letters { first, last })
+    }
+}
+
+impl Domain for Letters {
+    type Value = char;
+
+    fn size(&self) -> u64 {
+        u64::from(self.last) - u64::from(self.first) + 1
+    }
+
+    fn value(&self, index: u32) -> char {
+        char::from_u32(u32::from(self.first) + index).expect("a letter")
+    }
+
+    fn index(&self, value: &char) -> Option<u32> {
+        (self.first..=self.last)
+            .contains(value)
+            .then(|| u32::from(*value) - u32::from(self.first))
+    }
+
+    fn parse(&self, text: &str) -> Result<char, String> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if letter.is_ascii_alphabetic() => Ok(letter.to_ascii_lowercase()),
+            _ => Err(format!("'{text}' is not a single letter.")),
+        }
+    }
+
+    fn noun(&self) -> &'static str {
+        "letter"
+    }
+
+    fn too_small(&self) -> &'static str {
+        "Too early in the alphabet!"
+    }
+
+    fn too_big(&self) -> &'static str {
+        "Too late in the alphabet!"
+    }
+}
+
+// The words of a dictionary, in alphabetical order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Words {
+    words: Vec<String>,
+}
+
+impl Words {
+    // Takes one word per line. Words are not case sensitive, and blank lines
+    // and duplicates are left out.
+    pub fn from_list(list: &str) -> Result<Words, String> {
+        let mut words: Vec<String> = list
+            .lines()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        words.sort();
+        words.dedup();
+
+        check_size(words.len() as i128)?;
+        Ok(Words { words })
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.binary_search_by(|probe| probe.as_str().cmp(word)).is_ok()
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+impl Domain for Words {
+    type Value = String;
+
+    fn size(&self) -> u64 {
+        self.words.len() as u64
+    }
+
+    fn value(&self, index: u32) -> String {
+        self.words[index as usize].clone()
+    }
+
+    fn index(&self, value: &String) -> Option<u32> {
+        self.words.binary_search(value).ok().map(|index| index as u32)
+    }
+
+    fn parse(&self, text: &str) -> Result<String, String> {
+        let word = text.to_lowercase();
+        if self.contains(&word) {
+            Ok(word)
+        } else {
+            Err(format!("'{text}' is not in the dictionary."))
+        }
+    }
+
+    fn noun(&self) -> &'static str {
+        "word"
+    }
+
+    fn too_small(&self) -> &'static str {
+        "Too early in the dictionary!"
+    }
+
+    fn too_big(&self) -> &'static str {
+        "Too late in the dictionary!"
+    }
+}
+
+// A day in the calendar, written as `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    // Days since 1970-01-01.
+    days: i64,
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Date, String> {
+        let invalid = || format!("'{s}' is not a date like 2024-02-29");
+        let mut parts = s.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        let year: i64 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        let day: u32 = day.parse().map_err(|_| invalid())?;
+
+        // Keeps the calendar math below far away from overflowing.
+        if !(0..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+
+        // Days past the end of the month roll over into the next one, which
+        // is how a date like 2023-02-30 gets caught.
+        let days = days_from_civil(year, month, day);
+        if civil_from_days(days) != (year, month, day) {
+            return Err(invalid());
+        }
+        Ok(Date { days })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.days);
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+// Every day from `first` to `last`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dates {
+    first: Date,
+    last: Date,
+}
+
+impl Dates {
+    pub fn new(first: Date, last: Date) -> Result<Dates, String> {
+        if first > last {
+            return Err(format!("the range {first} to {last} is empty"));
+        }
+        check_size(i128::from(last.days - first.days) + 1)?;
+        Ok(Dates { first, last })
+    }
+}
+
+impl Domain for Dates {
+    type Value = Date;
+
+    fn size(&self) -> u64 {
+        (self.last.days - self.first.days) as u64 + 1
+    }
+
+    fn value(&self, index: u32) -> Date {
+        Date {
+            days: self.first.days + i64::from(index),
+        }
+    }
+
+    fn index(&self, value: &Date) -> Option<u32> {
+        (self.first..=self.last)
+            .contains(value)
+            .then(|| (value.days - self.first.days) as u32)
+    }
+
+    fn parse(&self, text: &str) -> Result<Date, String> {
+        text.parse().map_err(|e| format!("{e}."))
+    }
+
+    fn noun(&self) -> &'static str {
+        "date"
+    }
+
+    fn too_small(&self) -> &'static str {
+        "Too early!"
+    }
+
+    fn too_big(&self) -> &'static str {
+        "Too late!"
+    }
+}
@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+use rand::{Rng, RngCore};
+
+use crate::hint::{Clue, Hint, NoHint, Points};
+use crate::interval::Interval;
+use crate::turns::Turns;
+
+// The answer we give back to the player after every guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Less,
+    Greater,
+    Won,
+}
+
+impl Outcome {
+    // The comparison this answer was made from, `guess.cmp(&secret_number)`.
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Outcome::Less => Ordering::Less,
+            Outcome::Greater => Ordering::Greater,
+            Outcome::Won => Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Playing,
+    Won,
+    Lost,
+    GaveUp,
+    OutOfTime,
+}
+
+// Who decides what the answers are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Host {
+    // Picks the secret number up front and answers honestly.
+    Honest(u32),
+    // Never commits to a secret. Every answer keeps as many candidates alive
+    // as possible, so the player always needs the worst-case number of
+    // guesses. The answers stay consistent: any number left among the
+    // candidates is a secret that matches all of them.
+    Evil,
+}
+
+// A single round of the guessing game. Any front-end can drive it by calling
+// `guess` in a loop, see `Turns`.
+#[derive(Debug, Clone)]
+pub struct Game {
+    range: RangeInclusive<u32>,
+    host: Host,
+    candidates: Interval,
+    turns: Turns<u32, Outcome>,
+    clues: Vec<Clue>,
+}
+
+impl Game {
+    // Panics if `secret_number` is not inside `range`.
+    pub fn new(range: RangeInclusive<u32>, secret_number: u32) -> Game {
+        assert!(
+            range.contains(&secret_number),
+            "secret number {secret_number} is outside of {range:?}"
+        );
+
+        Game::with_host(range, Host::Honest(secret_number))
+    }
+
+    // A round against the evil host, see `Host::Evil`.
+    pub fn evil(range: RangeInclusive<u32>) -> Game {
+        Game::with_host(range, Host::Evil)
+    }
+
+    fn with_host(range: RangeInclusive<u32>, host: Host) -> Game {
+        Game {
+            candidates: Interval::new(&range),
+            range,
+            host,
+            turns: Turns::new(),
+            clues: Vec::new(),
+        }
+    }
+
+    // Draws the secret number from `rng`. Passing a seeded generator makes
+    // the round reproducible.
+    pub fn from_rng<R: RngCore + ?Sized>(range: RangeInclusive<u32>, rng: &mut R) -> Game {
+        let secret_number = rng.gen_range(range.clone());
+        Game::new(range, secret_number)
+    }
+
+    // Limits the round to `max_attempts` guesses, after which it is lost.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Game {
+        self.turns = self.turns.with_limit(max_attempts);
+        self
+    }
+
+    pub fn guess(&mut self, guess: u32) -> Outcome {
+        let ordering = match self.host {
+            Host::Honest(secret_number) => guess.cmp(&secret_number),
+            Host::Evil => self.evil_answer(guess),
+        };
+        let outcome = match ordering {
+            Ordering::Less => Outcome::Less,
+            Ordering::Greater => Outcome::Greater,
+            Ordering::Equal => Outcome::Won,
+        };
+
+        if self.turns.is_playing() {
+            self.candidates.narrow(guess, ordering);
+        }
+        self.turns.record(guess, outcome, outcome == Outcome::Won);
+
+        outcome
+    }
+
+    // Answers so that the larger part of the candidates survives. Only admits
+    // defeat when `guess` is the one candidate left.
+    fn evil_answer(&self, guess: u32) -> Ordering {
+        let (low, high) = (self.candidates.low(), self.candidates.high());
+
+        if guess < low {
+            Ordering::Less
+        } else if guess > high {
+            Ordering::Greater
+        } else if low == high {
+            Ordering::Equal
+        } else if guess - low >= high - guess {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    }
+
+    // Tells the player something about the secret number, for a price in
+    // points. Without a `hint` asked for, gives the next one in `Hint::ALL`
+    // that the player does not have yet.
+    pub fn hint(&mut self, hint: Option<Hint>) -> Result<Clue, NoHint> {
+        let secret_number = match (self.turns.state(), self.host) {
+            (State::Playing, Host::Honest(secret_number)) => secret_number,
+            (State::Playing, Host::Evil) => return Err(NoHint::Evil),
+            _ => return Err(NoHint::Over),
+        };
+
+        let given = |hint: &Hint| self.clues.iter().any(|clue| clue.hint() == *hint);
+        let hint = match hint {
+            Some(hint) if given(&hint) => return Err(NoHint::AlreadyGiven(hint)),
+            Some(hint) => hint,
+            None => *Hint::ALL.iter().find(|hint| !given(hint)).ok_or(NoHint::AllGiven)?,
+        };
+
+        let clue = hint.reveal(&self.range, secret_number);
+        self.clues.push(clue);
+        Ok(clue)
+    }
+
+    // Ends the round without a winner, e.g. when the player runs out of input.
+    pub fn give_up(&mut self) {
+        self.turns.end(State::GaveUp);
+    }
+
+    // Ends the round as lost because the time limit has passed.
+    pub fn run_out_of_time(&mut self) {
+        self.turns.end(State::OutOfTime);
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn host(&self) -> Host {
+        self.host
+    }
+
+    // The evil host has no secret until it is cornered, so it reports the
+    // smallest candidate left, which is consistent with every answer given.
+    pub fn secret_number(&self) -> u32 {
+        match self.host {
+            Host::Honest(secret_number) => secret_number,
+            Host::Evil => self.candidates.low(),
+        }
+    }
+
+    // The numbers that are still possible given every answer so far.
+    pub fn candidates(&self) -> Interval {
+        self.candidates
+    }
+
+    // Every guess made while the round was being played, with its answer.
+    pub fn history(&self) -> &[(u32, Outcome)] {
+        self.turns.history()
+    }
+
+    // Every hint given so far.
+    pub fn clues(&self) -> &[Clue] {
+        &self.clues
+    }
+
+    // Whether `secret_number` would have given exactly the answers and
+    // hints so far.
+    pub fn is_consistent(&self, secret_number: u32) -> bool {
+        self.range.contains(&secret_number)
+            && self
+                .history()
+                .iter()
+                .all(|&(guess, outcome)| guess.cmp(&secret_number) == outcome.ordering())
+            && self.clues.iter().all(|clue| clue.allows(secret_number))
+    }
+
+    // The points scored so far, see `hint::Points`.
+    pub fn points(&self) -> Points {
+        Points {
+            guesses: self.turns.count(),
+            hints: self.clues.iter().map(Clue::hint).collect(),
+            won: self.turns.state() == State::Won,
+        }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.turns.count()
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.turns.limit()
+    }
+
+    pub fn attempts_remaining(&self) -> Option<u32> {
+        self.turns.remaining()
+    }
+
+    pub fn state(&self) -> State {
+        self.turns.state()
+    }
+
+    pub fn turns(&self) -> &Turns<u32, Outcome> {
+        &self.turns
+    }
+}
+
+// The number of guesses binary search needs in the worst case to find any
+// number in `range`, i.e. ceil(log2(n + 1)) for a range of n numbers.
+pub fn fair_attempts(range: &RangeInclusive<u32>) -> u32 {
+    let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+    u64::BITS - size.leading_zeros()
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use rand::{Rng, RngCore};
+
+use crate::game::{fair_attempts, State};
+use crate::interval::Interval;
+use crate::turns::Turns;
+
+// How big a grid is along every axis, written like `10x10` or `8x8x8`.
+// Coordinates go from 0 to one less than the size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Size(pub Vec<u32>);
+
+impl Size {
+    pub fn dimensions(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn axes(&self) -> Vec<RangeInclusive<u32>> {
+        self.0.iter().map(|&size| 0..=size - 1).collect()
+    }
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Size, String> {
+        let sizes = s
+            .split(['x', 'X'])
+            .map(|size| match size.trim().parse::<u32>() {
+                Ok(0) => Err("every side needs at least one point".to_string()),
+                Ok(size) => Ok(size),
+                Err(_) => Err(format!("'{size}' is not a size, expected something like 10x10")),
+            })
+            .collect::<Result<Vec<u32>, String>>()?;
+
+        Ok(Size(sizes))
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, size) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "x")?;
+            }
+            write!(f, "{size}")?;
+        }
+        Ok(())
+    }
+}
+
+// A point on the grid, one coordinate per axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Point(pub Vec<u32>);
+
+impl Point {
+    // The square of the straight-line distance to `other`, which is enough to
+    // tell which of two points is closer without any rounding.
+    pub fn distance_squared(&self, other: &Point) -> u128 {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(&a, &b)| u128::from(a.abs_diff(b)).pow(2))
+            .sum()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, coordinate) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{coordinate}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+// What the host says after a guess that is not the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Clues {
+    // Which way to go along every axis, like "north-west".
+    #[default]
+    Directions,
+    // Only whether the guess is closer than the one before.
+    Warmth,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warmth {
+    // The first guess has nothing to be compared with.
+    First,
+    Hotter,
+    Colder,
+    AsWarm,
+}
+
+// The answer to a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Found,
+    // `guess.cmp(&secret)` for every axis, like `Outcome::ordering` for a
+    // single number.
+    Directions(Vec<Ordering>),
+    Warmth(Warmth),
+}
+
+impl Answer {
+    // How the answer reads, e.g. "The secret is north-west." or "Hotter!".
+    pub fn describe(&self) -> String {
+        match self {
+            Answer::Found => "Found it!".to_string(),
+            Answer::Directions(orderings) => format!("The secret is {}.", direction(orderings)),
+            Answer::Warmth(Warmth::First) => "Cold or hot? Guess again to find out.".to_string(),
+            Answer::Warmth(Warmth::Hotter) => "Hotter!".to_string(),
+            Answer::Warmth(Warmth::Colder) => "Colder!".to_string(),
+            Answer::Warmth(Warmth::AsWarm) => "Just as warm.".to_string(),
+        }
+    }
+}
+
+// Turns the comparisons into compass directions: east and west along the
+// first axis, north and south along the second, up and down along the
+// third. Any further axes are called by their number.
+pub fn direction(orderings: &[Ordering]) -> String {
+    // The secret is in the direction of larger coordinates where the guess
+    // is less than it.
+    let word = |axis: usize, larger: &'static str, smaller: &'static str| -> Option<String> {
+        match orderings.get(axis)? {
+            Ordering::Less => Some(larger.to_string()),
+            Ordering::Greater => Some(smaller.to_string()),
+            Ordering::Equal => None,
+        }
+    };
+
+    // "north-west" reads better than "west-north".
+    let compass: Vec<String> = [word(1, "north", "south"), word(0, "east", "west")]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let mut parts = Vec::new();
+    if !compass.is_empty() {
+        parts.push(compass.join("-"));
+    }
+    parts.extend(word(2, "up", "down"));
+    for (axis, ordering) in orderings.iter().enumerate().skip(3) {
+        match ordering {
+            Ordering::Less => parts.push(format!("higher on axis {}", axis + 1)),
+            Ordering::Greater => parts.push(format!("lower on axis {}", axis + 1)),
+            Ordering::Equal => {}
+        }
+    }
+
+    parts.join(", ")
+}
+
+// Why a line typed is no point on the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejected {
+    WrongDimensions { expected: usize },
+    NotANumber(String),
+    OutOfRange { axis: usize, max: u32 },
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejected::WrongDimensions { expected } => write!(f, "A point has {expected} coordinates."),
+            Rejected::NotANumber(text) => write!(f, "'{text}' is not a coordinate."),
+            Rejected::OutOfRange { axis, max } => {
+                write!(f, "Coordinate {axis} has to be between 0 and {max}.")
+            }
+        }
+    }
+}
+
+// A round of finding a point on a grid of any number of dimensions.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    size: Size,
+    secret: Point,
+    clues: Clues,
+    // The coordinates still possible along every axis, given the directions
+    // so far. Warmth says too little to narrow them down.
+    candidates: Vec<Interval>,
+    turns: Turns<Point, Answer>,
+}
+
+impl Grid {
+    // Panics if `secret` is not on the grid.
+    pub fn new(size: Size, secret: Point, clues: Clues) -> Grid {
+        let axes = size.axes();
+        assert!(
+            secret.0.len() == axes.len() && secret.0.iter().zip(&axes).all(|(c, axis)| axis.contains(c)),
+            "secret point {secret} is not on a {size} grid"
+        );
+
+        Grid {
+            candidates: axes.iter().map(Interval::new).collect(),
+            size,
+            secret,
+            clues,
+            turns: Turns::new(),
+        }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(size: Size, clues: Clues, rng: &mut R) -> Grid {
+        let secret = Point(size.axes().into_iter().map(|axis| rng.gen_range(axis)).collect());
+        Grid::new(size, secret, clues)
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Grid {
+        self.turns = self.turns.with_limit(max_attempts);
+        self
+    }
+
+    // Reads a point like `3, 4`, `3 4` or `(3, 4)`.
+    pub fn parse(&self, text: &str) -> Result<Point, Rejected> {
+        let text = text.trim().trim_start_matches('(').trim_end_matches(')');
+        let coordinates: Vec<&str> = text
+            .split([',', ' ', '\t'])
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        let expected = self.size.dimensions();
+        if coordinates.len() != expected {
+            return Err(Rejected::WrongDimensions { expected });
+        }
+
+        let mut point = Vec::with_capacity(expected);
+        for (i, (text, axis)) in coordinates.iter().zip(self.size.axes()).enumerate() {
+            let coordinate: u32 = text.parse().map_err(|_| Rejected::NotANumber(text.to_string()))?;
+            if !axis.contains(&coordinate) {
+                return Err(Rejected::OutOfRange {
+                    axis: i + 1,
+                    max: *axis.end(),
+                });
+            }
+            point.push(coordinate);
+        }
+        Ok(Point(point))
+    }
+
+    pub fn guess(&mut self, guess: &Point) -> Answer {
+        let orderings: Vec<Ordering> = guess.0.iter().zip(&self.secret.0).map(|(g, s)| g.cmp(s)).collect();
+
+        let answer = if orderings.iter().all(|&ordering| ordering == Ordering::Equal) {
+            Answer::Found
+        } else {
+            match self.clues {
+                Clues::Directions => Answer::Directions(orderings.clone()),
+                Clues::Warmth => Answer::Warmth(self.warmth(guess)),
+            }
+        };
+
+        if self.turns.is_playing() && (self.clues == Clues::Directions || answer == Answer::Found) {
+            for ((candidates, &coordinate), &ordering) in self.candidates.iter_mut().zip(&guess.0).zip(&orderings) {
+                candidates.narrow(coordinate, ordering);
+            }
+        }
+        self.turns.record(guess.clone(), answer.clone(), answer == Answer::Found);
+
+        answer
+    }
+
+    fn warmth(&self, guess: &Point) -> Warmth {
+        let Some((previous, _)) = self.turns.history().last() else {
+            return Warmth::First;
+        };
+        match guess
+            .distance_squared(&self.secret)
+            .cmp(&previous.distance_squared(&self.secret))
+        {
+            Ordering::Less => Warmth::Hotter,
+            Ordering::Greater => Warmth::Colder,
+            Ordering::Equal => Warmth::AsWarm,
+        }
+    }
+
+    pub fn give_up(&mut self) {
+        self.turns.end(State::GaveUp);
+    }
+
+    pub fn size(&self) -> &Size {
+        &self.size
+    }
+
+    pub fn secret(&self) -> &Point {
+        &self.secret
+    }
+
+    pub fn clues(&self) -> Clues {
+        self.clues
+    }
+
+    // The coordinates still possible along every axis.
+    pub fn candidates(&self) -> &[Interval] {
+        &self.candidates
+    }
+
+    pub fn turns(&self) -> &Turns<Point, Answer> {
+        &self.turns
+    }
+}
+
+// With directions every axis is searched at the same time, so the grid needs
+// as many guesses as its longest side does on its own.
+pub fn fair_grid_attempts(size: &Size) -> u32 {
+    size.axes().iter().map(fair_attempts).max().unwrap_or(0)
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// Every round starts with this many points. Guesses and hints are paid for
+// out of them, and only a won round keeps what is left.
+pub const START_POINTS: u32 = 100;
+pub const GUESS_COST: u32 = 5;
+
+// Something the player can ask to be told about the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    Parity,
+    Half,
+    Quarter,
+    Divisible(u32),
+    DigitSum,
+}
+
+impl Hint {
+    // The order in which a plain `hint` hands them out, cheapest first.
+    pub const ALL: [Hint; 7] = [
+        Hint::Parity,
+        Hint::Half,
+        Hint::Divisible(3),
+        Hint::Divisible(5),
+        Hint::Divisible(7),
+        Hint::Quarter,
+        Hint::DigitSum,
+    ];
+
+    // What the hint costs in points. Hints that rule out more numbers cost
+    // more.
+    pub fn cost(self) -> u32 {
+        match self {
+            Hint::Parity | Hint::Half | Hint::Divisible(_) => 10,
+            Hint::Quarter => 20,
+            Hint::DigitSum => 25,
+        }
+    }
+
+    // Works out the clue about `secret_number`, a number in `range`.
+    pub fn reveal(self, range: &RangeInclusive<u32>, secret_number: u32) -> Clue {
+        match self {
+            Hint::Parity => Clue::Parity {
+                even: secret_number.is_multiple_of(2),
+            },
+            Hint::Half => {
+                let (half, low, high) = part(range, 2, secret_number);
+                Clue::Half { half, low, high }
+            }
+            Hint::Quarter => {
+                let (quarter, low, high) = part(range, 4, secret_number);
+                Clue::Quarter { quarter, low, high }
+            }
+            Hint::Divisible(by) => Clue::Divisible {
+                by,
+                divisible: secret_number.is_multiple_of(by),
+            },
+            Hint::DigitSum => Clue::DigitSum {
+                sum: digit_sum(secret_number),
+            },
+        }
+    }
+}
+
+impl FromStr for Hint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Hint, String> {
+        match s {
+            "parity" => Ok(Hint::Parity),
+            "half" => Ok(Hint::Half),
+            "quarter" => Ok(Hint::Quarter),
+            "div3" => Ok(Hint::Divisible(3)),
+            "div5" => Ok(Hint::Divisible(5)),
+            "div7" => Ok(Hint::Divisible(7)),
+            "digits" => Ok(Hint::DigitSum),
+            _ => Err(format!(
+                "unknown hint '{s}', expected parity, half, quarter, div3, div5, div7 or digits"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::Parity => write!(f, "parity"),
+            Hint::Half => write!(f, "half"),
+            Hint::Quarter => write!(f, "quarter"),
+            Hint::Divisible(by) => write!(f, "div{by}"),
+            Hint::DigitSum => write!(f, "digits"),
+        }
+    }
+}
+
+// Which of `parts` equal parts of `range` `number` is in, counting from 1,
+// with the bounds of that part.
+fn part(range: &RangeInclusive<u32>, parts: u64, number: u32) -> (u32, u32, u32) {
+    let start = u64::from(*range.start());
+    let size = u64::from(*range.end()) - start + 1;
+
+    let index = (u64::from(number) - start) * parts / size;
+    let low = start + (index * size).div_ceil(parts);
+    let high = start + ((index + 1) * size).div_ceil(parts) - 1;
+    (index as u32 + 1, low as u32, high as u32)
+}
+
+fn digit_sum(mut number: u32) -> u32 {
+    let mut sum = 0;
+    while number > 0 {
+        sum += number % 10;
+        number /= 10;
+    }
+    sum
+}
+
+// What a hint told about the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Clue {
+    Parity { even: bool },
+    // Which half or quarter of the range, counting from 1, and its bounds.
+    Half { half: u32, low: u32, high: u32 },
+    Quarter { quarter: u32, low: u32, high: u32 },
+    Divisible { by: u32, divisible: bool },
+    DigitSum { sum: u32 },
+}
+
+impl Clue {
+    pub fn hint(&self) -> Hint {
+        match *self {
+            Clue::Parity { .. } => Hint::Parity,
+            Clue::Half { .. } => Hint::Half,
+            Clue::Quarter { .. } => Hint::Quarter,
+            Clue::Divisible { by, .. } => Hint::Divisible(by),
+            Clue::DigitSum { .. } => Hint::DigitSum,
+        }
+    }
+
+    // Whether `number` fits what the clue says about the secret number.
+    pub fn allows(&self, number: u32) -> bool {
+        match *self {
+            Clue::Parity { even } => number.is_multiple_of(2) == even,
+            Clue::Half { low, high, .. } | Clue::Quarter { low, high, .. } => (low..=high).contains(&number),
+            Clue::Divisible { by, divisible } => number.is_multiple_of(by) == divisible,
+            Clue::DigitSum { sum } => digit_sum(number) == sum,
+        }
+    }
+}
+
+impl fmt::Display for Clue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Clue::Parity { even: true } => write!(f, "The number is even."),
+            Clue::Parity { even: false } => write!(f, "The number is odd."),
+            Clue::Half { half, low, high } => {
+                let which = if half == 1 { "lower" } else { "upper" };
+                write!(f, "The number is in the {which} half, {low}-{high}.")
+            }
+            Clue::Quarter { quarter, low, high } => {
+                let which = ["first", "second", "third", "fourth"][quarter as usize - 1];
+                write!(f, "The number is in the {which} quarter, {low}-{high}.")
+            }
+            Clue::Divisible { by, divisible: true } => write!(f, "The number is divisible by {by}."),
+            Clue::Divisible { by, divisible: false } => write!(f, "The number is not divisible by {by}."),
+            Clue::DigitSum { sum } => write!(f, "The digits of the number add up to {sum}."),
+        }
+    }
+}
+
+// Why no hint was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoHint {
+    // The evil host has no secret number to give hints about.
+    Evil,
+    AlreadyGiven(Hint),
+    AllGiven,
+    Over,
+}
+
+impl NoHint {
+    // A short name for the `plain` and `json` output.
+    pub fn name(self) -> &'static str {
+        match self {
+            NoHint::Evil => "evil",
+            NoHint::AlreadyGiven(_) => "already_given",
+            NoHint::AllGiven => "all_given",
+            NoHint::Over => "over",
+        }
+    }
+}
+
+impl fmt::Display for NoHint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NoHint::Evil => write!(f, "The evil host does not give hints."),
+            NoHint::AlreadyGiven(hint) => write!(f, "You already had the {hint} hint."),
+            NoHint::AllGiven => write!(f, "There are no hints left."),
+            NoHint::Over => write!(f, "The round is over."),
+        }
+    }
+}
+
+// The points of a round, and what they were spent on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Points {
+    pub guesses: u32,
+    pub hints: Vec<Hint>,
+    pub won: bool,
+}
+
+impl Points {
+    pub fn guess_cost(&self) -> u32 {
+        self.guesses * GUESS_COST
+    }
+
+    pub fn hint_cost(&self) -> u32 {
+        self.hints.iter().map(|hint| hint.cost()).sum()
+    }
+
+    // What is left of `START_POINTS` after paying for every guess and hint,
+    // or nothing if the round was not won.
+    pub fn total(&self) -> u32 {
+        if self.won {
+            START_POINTS.saturating_sub(self.guess_cost() + self.hint_cost())
+        } else {
+            0
+        }
+    }
+}
+
+// The sum the points were worked out with, e.g.
+// `100 - 4 guesses x 5 - parity hint 10 = 70 points`.
+impl fmt::Display for Points {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let guesses = if self.guesses == 1 { "guess" } else { "guesses" };
+        write!(f, "{START_POINTS} - {} {guesses} x {GUESS_COST}", self.guesses)?;
+        for hint in &self.hints {
+            write!(f, " - {hint} hint {}", hint.cost())?;
+        }
+        if self.won {
+            write!(f, " = {} points", self.total())
+        } else {
+            write!(f, ", but only a won round scores: 0 points")
+        }
+    }
+}
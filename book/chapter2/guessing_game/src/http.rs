@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::game::{Game, State};
+use crate::net::Deadline;
+use crate::report::{outcome_name, state_name};
+
+// The largest request body we are willing to read.
+const MAX_BODY: usize = 64 * 1024;
+
+// The longest request line or header we read, and how many headers. Like the
+// line protocol's `MAX_LINE`, they keep a client from filling up memory.
+pub const MAX_LINE: u64 = 8 * 1024;
+pub const MAX_HEADERS: usize = 100;
+
+// How long a client has to send its whole request, however slowly the bytes
+// trickle in.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// The games being played through the API, kept in memory. A game nobody has
+// looked at for `expiry` is thrown away.
+pub struct Store {
+    games: Mutex<HashMap<u64, Entry>>,
+    next_id: Mutex<u64>,
+    range: RangeInclusive<u32>,
+    expiry: Duration,
+}
+
+struct Entry {
+    game: Game,
+    last_used: Instant,
+}
+
+impl Store {
+    // `range` is used for games created without a range of their own.
+    pub fn new(range: RangeInclusive<u32>, expiry: Duration) -> Store {
+        Store {
+            games: Mutex::new(HashMap::new()),
+            next_id: Mutex::new(1),
+            range,
+            expiry,
+        }
+    }
+
+    fn insert(&self, game: Game) -> u64 {
+        let mut next_id = self.next_id.lock().unwrap();
+        let id = *next_id;
+        *next_id += 1;
+
+        let entry = Entry {
+            game,
+            last_used: Instant::now(),
+        };
+        // Purging here too keeps clients that only ever create games from
+        // filling up the store.
+        let mut games = self.games.lock().unwrap();
+        self.remove_expired(&mut games);
+        games.insert(id, entry);
+        id
+    }
+
+    // How many games are kept, expired ones that were not purged yet
+    // included.
+    pub fn count(&self) -> usize {
+        self.games.lock().unwrap().len()
+    }
+
+    // Runs `f` on the game with `id`, if it has not expired.
+    fn with_game<T>(&self, id: u64, f: impl FnOnce(&mut Game) -> T) -> Option<T> {
+        let mut games = self.games.lock().unwrap();
+        self.remove_expired(&mut games);
+
+        let entry = games.get_mut(&id)?;
+        entry.last_used = Instant::now();
+        Some(f(&mut entry.game))
+    }
+
+    fn remove_expired(&self, games: &mut HashMap<u64, Entry>) {
+        let expiry = self.expiry;
+        games.retain(|_, entry| entry.last_used.elapsed() < expiry);
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewGame {
+    min: Option<u32>,
+    max: Option<u32>,
+    seed: Option<u64>,
+    max_attempts: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: u32,
+}
+
+#[derive(Serialize)]
+struct GameView {
+    id: u64,
+    min: u32,
+    max: u32,
+    state: &'static str,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    history: Vec<GuessView>,
+    // Only revealed once the game is over.
+    secret: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct GuessView {
+    guess: u32,
+    result: &'static str,
+}
+
+impl GameView {
+    fn new(id: u64, game: &Game) -> GameView {
+        GameView {
+            id,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            state: state_name(game.state()),
+            attempts: game.attempts(),
+            max_attempts: game.max_attempts(),
+            history: game
+                .history()
+                .iter()
+                .map(|&(guess, outcome)| GuessView {
+                    guess,
+                    result: outcome_name(outcome),
+                })
+                .collect(),
+            secret: (game.state() != State::Playing).then(|| game.secret_number()),
+        }
+    }
+}
+
+// A response, before it is written out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, body: T) -> Response {
+        Response {
+            status,
+            body: serde_json::to_value(body).expect("responses can always be serialized"),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response::json(status, json!({ "error": message }))
+    }
+}
+
+// Routes a single request to the games in `store`.
+pub fn handle_request(store: &Store, method: &str, path: &str, body: &[u8]) -> Response {
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (method, &segments[..]) {
+        ("POST", ["games"]) => create_game(store, body),
+        ("GET", ["games", id]) => match id.parse() {
+            Ok(id) => store
+                .with_game(id, |game| Response::json(200, GameView::new(id, game)))
+                .unwrap_or_else(|| Response::error(404, "no such game")),
+            Err(_) => Response::error(404, "no such game"),
+        },
+        ("POST", ["games", id, "guesses"]) => match id.parse() {
+            Ok(id) => make_guess(store, id, body),
+            Err(_) => Response::error(404, "no such game"),
+        },
+        (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => {
+            Response::error(405, "method not allowed")
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn create_game(store: &Store, body: &[u8]) -> Response {
+    let request: NewGame = if body.iter().all(u8::is_ascii_whitespace) {
+        NewGame::default()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return Response::error(400, &format!("invalid request: {e}")),
+        }
+    };
+
+    let min = request.min.unwrap_or(*store.range.start());
+    let max = request.max.unwrap_or(*store.range.end());
+    if min > max {
+        return Response::error(400, "min must not be greater than max");
+    }
+    if request.max_attempts == Some(0) {
+        return Response::error(400, "max_attempts must be at least 1");
+    }
+
+    let mut rng = match request.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut game = Game::from_rng(min..=max, &mut rng);
+    if let Some(max_attempts) = request.max_attempts {
+        game = game.with_max_attempts(max_attempts);
+    }
+
+    let view = GameView::new(0, &game);
+    let id = store.insert(game);
+    Response::json(201, GameView { id, ..view })
+}
+
+fn make_guess(store: &Store, id: u64, body: &[u8]) -> Response {
+    let request: NewGuess = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return Response::error(400, &format!("invalid request: {e}")),
+    };
+
+    store
+        .with_game(id, |game| {
+            if game.state() != State::Playing {
+                return Response::error(409, "the game is over");
+            }
+            // Like at the prompt, a guess outside of the range costs nothing.
+            let range = game.range();
+            if !range.contains(&request.guess) {
+                let message = format!("the guess must be between {} and {}", range.start(), range.end());
+                return Response::error(422, &message);
+            }
+
+            let outcome = game.guess(request.guess);
+            Response::json(
+                200,
+                json!({
+                    "guess": request.guess,
+                    "result": outcome_name(outcome),
+                    "attempt": game.attempts(),
+                    "game": GameView::new(id, game),
+                }),
+            )
+        })
+        .unwrap_or_else(|| Response::error(404, "no such game"))
+}
+
+// Reads one HTTP/1.1 request from `stream`, answers it and closes the
+// connection. The request has to arrive within `timeout`.
+pub fn handle_connection(store: &Store, stream: TcpStream, timeout: Duration) -> io::Result<()> {
+    let mut reader = BufReader::new(Deadline::new(stream.try_clone()?, timeout));
+    let mut writer = stream;
+
+    // Whether part of the request may still be unread.
+    let mut unread = true;
+    let response = match read_request(&mut reader) {
+        Ok(Ok(request)) => {
+            unread = false;
+            handle_request(store, &request.method, &request.path, &request.body)
+        }
+        Ok(Err(response)) => response,
+        Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+            Response::error(408, "the request took too long")
+        }
+        Err(e) => return Err(e),
+    };
+
+    let body = response.body.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason(response.status),
+        body.len()
+    )?;
+    writer.flush()?;
+
+    // Closing with bytes left unread resets the connection, and the client
+    // may lose the response. So finish sending first and throw away what is
+    // left, as long as the deadline lasts.
+    if unread {
+        writer.shutdown(Shutdown::Write)?;
+        let _ = io::copy(&mut reader.take(MAX_BODY as u64), &mut io::sink());
+    }
+    Ok(())
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+// Reads a request, or gives the response to send back if it is broken.
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Result<Request, Response>> {
+    let mut request_line = String::new();
+    if !read_line(reader, &mut request_line)? {
+        return Ok(Err(Response::error(414, "request line too long")));
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/1.") => {
+            (method.to_string(), path.to_string())
+        }
+        _ => return Ok(Err(Response::error(400, "malformed request line"))),
+    };
+
+    let mut content_length = 0;
+    let mut headers = 0;
+    loop {
+        let mut header = String::new();
+        if !read_line(reader, &mut header)? {
+            return Ok(Err(Response::error(431, "header too long")));
+        }
+        if header.is_empty() {
+            return Ok(Err(Response::error(400, "unexpected end of headers")));
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Ok(Err(Response::error(431, "too many headers")));
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = match value.trim().parse() {
+                    Ok(length) => length,
+                    Err(_) => return Ok(Err(Response::error(400, "invalid Content-Length"))),
+                };
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Ok(Err(Response::error(413, "request body too large")));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Ok(Request { method, path, body }))
+}
+
+// Reads a line of at most `MAX_LINE` bytes into `line`, or returns `false`
+// if it is longer.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<bool> {
+    let read = reader.by_ref().take(MAX_LINE).read_line(line)?;
+    Ok(line.ends_with('\n') || (read as u64) < MAX_LINE)
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        414 => "URI Too Long",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+// Accepts connections on `listener` forever, answering each on its own thread.
+// A client gets `timeout` to send its request.
+pub fn serve(listener: TcpListener, store: Arc<Store>, timeout: Duration) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to accept a connection: {e}");
+                continue;
+            }
+        };
+
+        let store = Arc::clone(&store);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(&store, stream, timeout) {
+                eprintln!("Failed to answer a request: {e}");
+            }
+        });
+    }
+
+    Ok(())
+}
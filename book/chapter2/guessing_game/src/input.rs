@@ -0,0 +1,183 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::hint::Hint;
+
+// What the player typed at the prompt: a guess or one of the commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Guess(u32),
+    Quit,
+    // A particular hint, or the next one.
+    Hint(Option<Hint>),
+    History,
+    Help,
+    Save,
+}
+
+// The commands that can be typed instead of a guess, with what they do.
+pub const COMMANDS: [(&str, &str); 5] = [
+    ("quit", "give up and show the secret number"),
+    ("hint", "learn something about the number, for a price in points"),
+    ("history", "list the guesses so far"),
+    ("help", "show this list"),
+    ("save", "save the round and quit, to be resumed later"),
+];
+
+// Why a line is not a guess the game can take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Empty,
+    NotANumber,
+    Negative,
+    NotWhole,
+    Several,
+    Overflow,
+    OutOfRange { guess: u32, min: u32, max: u32 },
+    UnknownHint(String),
+}
+
+impl InputError {
+    // A short name for the `plain` and `json` output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputError::Empty => "empty",
+            InputError::NotANumber => "not_a_number",
+            InputError::Negative => "negative",
+            InputError::NotWhole => "not_whole",
+            InputError::Several => "several",
+            InputError::Overflow => "overflow",
+            InputError::OutOfRange { .. } => "out_of_range",
+            InputError::UnknownHint(_) => "unknown_hint",
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "Please type a guess, or 'help' for the commands."),
+            InputError::NotANumber => write!(f, "That is not a number, type 'help' for the commands."),
+            InputError::Negative => write!(f, "The secret number is never negative."),
+            InputError::NotWhole => write!(f, "The secret number is a whole number."),
+            InputError::Several => write!(f, "Please guess one number at a time."),
+            InputError::Overflow => write!(f, "That number is far too big."),
+            InputError::OutOfRange { guess, min, max } => {
+                write!(f, "{guess} is not between {min} and {max}.")
+            }
+            InputError::UnknownHint(name) => write!(
+                f,
+                "There is no '{name}' hint, try parity, half, quarter, div3, div5, div7 or digits."
+            ),
+        }
+    }
+}
+
+// Reads a line typed at the prompt of a round played in `range`.
+//
+// Besides plain digits, a guess may have a leading `+` and its digits may be
+// grouped with `_` or `,`, as in `1_000` or `1,000`. Commands are not case
+// sensitive, and `hint` may be followed by the name of a hint.
+pub fn parse(line: &str, range: &RangeInclusive<u32>) -> Result<Line, InputError> {
+    if let Some(command) = command(line) {
+        return command;
+    }
+
+    let line = line.trim();
+    if line.is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let guess = parse_number(line)?;
+    if !range.contains(&guess) {
+        return Err(InputError::OutOfRange {
+            guess,
+            min: *range.start(),
+            max: *range.end(),
+        });
+    }
+
+    Ok(Line::Guess(guess))
+}
+
+// The command on `line`, if it is one and not a guess.
+pub fn command(line: &str) -> Option<Result<Line, InputError>> {
+    let lowercase = line.trim().to_lowercase();
+
+    if let Some(name) = lowercase.strip_prefix("hint ") {
+        return Some(
+            name.trim()
+                .parse()
+                .map(|hint| Line::Hint(Some(hint)))
+                .map_err(|_| InputError::UnknownHint(name.trim().to_string())),
+        );
+    }
+
+    let command = match lowercase.as_str() {
+        "quit" | "exit" | "q" => Line::Quit,
+        "hint" => Line::Hint(None),
+        "history" => Line::History,
+        "help" | "?" => Line::Help,
+        "save" => Line::Save,
+        _ => return None,
+    };
+    Some(Ok(command))
+}
+
+fn parse_number(text: &str) -> Result<u32, InputError> {
+    if text.split_whitespace().count() > 1 {
+        return Err(if text.split_whitespace().all(looks_numeric) {
+            InputError::Several
+        } else {
+            InputError::NotANumber
+        });
+    }
+
+    let (negative, number) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    // `42.0` is still 42, `42.5` is no guess at all.
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(InputError::NotANumber);
+    }
+    let digits = digits(whole).ok_or(InputError::NotANumber)?;
+
+    let is_zero = |digits: &str| digits.bytes().all(|digit| digit == b'0');
+    if negative && !(is_zero(&digits) && is_zero(fraction)) {
+        return Err(InputError::Negative);
+    }
+    if !is_zero(fraction) {
+        return Err(InputError::NotWhole);
+    }
+
+    digits.parse().map_err(|_| InputError::Overflow)
+}
+
+// The digits of `text` without the `_` or `,` they are grouped with, if it is
+// a number at all. A separator has to sit between two digits.
+fn digits(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut digits = String::with_capacity(bytes.len());
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'0'..=b'9' => digits.push(char::from(byte)),
+            b'_' | b',' if i > 0 && bytes[i - 1].is_ascii_digit() && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {}
+            _ => return None,
+        }
+    }
+
+    if digits.is_empty() {
+        None
+    } else {
+        Some(digits)
+    }
+}
+
+fn looks_numeric(word: &str) -> bool {
+    word.trim_start_matches(['+', '-']).bytes().any(|byte| byte.is_ascii_digit())
+        && word.bytes().all(|byte| byte.is_ascii_digit() || b"+-_,.".contains(&byte))
+}
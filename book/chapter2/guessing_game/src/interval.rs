@@ -0,0 +1,76 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+// The numbers that are still possible secrets, given every answer so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    low: u32,
+    high: u32,
+    empty: bool,
+}
+
+impl Interval {
+    pub fn new(range: &RangeInclusive<u32>) -> Interval {
+        Interval {
+            low: *range.start(),
+            high: *range.end(),
+            empty: range.is_empty(),
+        }
+    }
+
+    // Removes every candidate that contradicts `guess.cmp(&secret) == answer`.
+    pub fn narrow(&mut self, guess: u32, answer: Ordering) {
+        match answer {
+            Ordering::Less => match guess.checked_add(1) {
+                Some(low) => self.low = self.low.max(low),
+                None => self.empty = true,
+            },
+            Ordering::Greater => match guess.checked_sub(1) {
+                Some(high) => self.high = self.high.min(high),
+                None => self.empty = true,
+            },
+            Ordering::Equal => {
+                if self.contains(guess) {
+                    self.low = guess;
+                    self.high = guess;
+                } else {
+                    self.empty = true;
+                }
+            }
+        }
+
+        if self.low > self.high {
+            self.empty = true;
+        }
+    }
+
+    pub fn low(&self) -> u32 {
+        self.low
+    }
+
+    pub fn high(&self) -> u32 {
+        self.high
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        !self.empty && self.low <= number && number <= self.high
+    }
+
+    // The number of candidates left, which does not fit a `u32` for the full
+    // `0..=u32::MAX` range.
+    pub fn len(&self) -> u64 {
+        if self.empty {
+            0
+        } else {
+            u64::from(self.high) - u64::from(self.low) + 1
+        }
+    }
+
+    pub fn midpoint(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+}
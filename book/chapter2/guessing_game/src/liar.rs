@@ -0,0 +1,300 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::game::{Outcome, State};
+use crate::turns::Turns;
+
+// How often a host with a limit on its lies tells one, until the limit is
+// reached.
+const LIE_CHANCE_WITH_LIMIT: f64 = 0.25;
+
+// The most lies `--lies` allows. More would only make the rounds endless.
+pub const MAX_LIES: u32 = 100;
+
+// How the host lies, as in the Rényi–Ulam game. A lie turns "too small" into
+// "too big" or the other way round; the host never claims a wrong guess is
+// right or a right one is wrong.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Noise {
+    // Every answer is a lie with this probability.
+    Chance(f64),
+    // At most this many answers are lies.
+    Limit(u32),
+}
+
+impl fmt::Display for Noise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // Rounded to a tenth of a percent, so 0.07 is not 7.000000000000001.
+            Noise::Chance(p) => write!(f, "lies {}% of the time", (p * 1000.0).round() / 10.0),
+            Noise::Limit(1) => write!(f, "lies at most once"),
+            Noise::Limit(k) => write!(f, "lies up to {k} times"),
+        }
+    }
+}
+
+// What the host said about a guess, and whether it was a lie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Told {
+    pub outcome: Outcome,
+    pub lie: bool,
+}
+
+// A round against a host that sometimes lies.
+#[derive(Debug, Clone)]
+pub struct NoisyGame {
+    range: RangeInclusive<u32>,
+    secret_number: u32,
+    noise: Noise,
+    // Decides when to lie, drawn from the round's generator so a seed still
+    // makes the round reproducible.
+    rng: StdRng,
+    turns: Turns<u32, Told>,
+}
+
+impl NoisyGame {
+    // Panics if `secret_number` is not inside `range`.
+    pub fn new<R: RngCore + ?Sized>(range: RangeInclusive<u32>, secret_number: u32, noise: Noise, rng: &mut R) -> NoisyGame {
+        assert!(
+            range.contains(&secret_number),
+            "secret number {secret_number} is outside of {range:?}"
+        );
+
+        NoisyGame {
+            range,
+            secret_number,
+            noise,
+            rng: StdRng::seed_from_u64(rng.gen()),
+            turns: Turns::new(),
+        }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(range: RangeInclusive<u32>, noise: Noise, rng: &mut R) -> NoisyGame {
+        let secret_number = rng.gen_range(range.clone());
+        NoisyGame::new(range, secret_number, noise, rng)
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> NoisyGame {
+        self.turns = self.turns.with_limit(max_attempts);
+        self
+    }
+
+    pub fn guess(&mut self, guess: u32) -> Outcome {
+        let truth = match guess.cmp(&self.secret_number) {
+            Ordering::Less => Outcome::Less,
+            Ordering::Greater => Outcome::Greater,
+            Ordering::Equal => Outcome::Won,
+        };
+        if !self.turns.is_playing() {
+            return truth;
+        }
+
+        let lie = truth != Outcome::Won && self.wants_to_lie();
+        let outcome = match (truth, lie) {
+            (Outcome::Less, true) => Outcome::Greater,
+            (Outcome::Greater, true) => Outcome::Less,
+            (outcome, _) => outcome,
+        };
+        self.turns.record(guess, Told { outcome, lie }, outcome == Outcome::Won);
+        outcome
+    }
+
+    fn wants_to_lie(&mut self) -> bool {
+        match self.noise {
+            Noise::Chance(p) => self.rng.gen_bool(p),
+            Noise::Limit(k) => self.lies() < k && self.rng.gen_bool(LIE_CHANCE_WITH_LIMIT),
+        }
+    }
+
+    pub fn give_up(&mut self) {
+        self.turns.end(State::GaveUp);
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn noise(&self) -> Noise {
+        self.noise
+    }
+
+    pub fn secret_number(&self) -> u32 {
+        self.secret_number
+    }
+
+    // Every guess with what the host said. Which answers were lies is only
+    // to be shown once the round is over.
+    pub fn turns(&self) -> &Turns<u32, Told> {
+        &self.turns
+    }
+
+    // The guesses that were answered with a lie.
+    pub fn lied_about(&self) -> Vec<u32> {
+        self.turns
+            .history()
+            .iter()
+            .filter(|(_, told)| told.lie)
+            .map(|&(guess, _)| guess)
+            .collect()
+    }
+
+    pub fn lies(&self) -> u32 {
+        self.lied_about().len() as u32
+    }
+}
+
+// Finds the number in spite of the lies. It counts for every candidate how
+// many answers would have been lies if it were the secret, and weighs it by
+// how likely that many lies are. Every guess is the weighted median, so that
+// each answer, if true, rules out half of the weight.
+pub struct Ulam {
+    first: u32,
+    noise: Noise,
+    // For every number in the range, how many answers so far it contradicts,
+    // or `None` once it can no longer be the secret.
+    lies: Vec<Option<u32>>,
+}
+
+impl Ulam {
+    pub fn new(range: &RangeInclusive<u32>, noise: Noise) -> Ulam {
+        Ulam {
+            first: *range.start(),
+            noise,
+            lies: vec![Some(0); (range.end() - range.start()) as usize + 1],
+        }
+    }
+
+    // How much more likely a candidate is than one that needs a lie more.
+    fn ratio(&self) -> f64 {
+        match self.noise {
+            Noise::Chance(p) => p / (1.0 - p),
+            Noise::Limit(_) => LIE_CHANCE_WITH_LIMIT / (1.0 - LIE_CHANCE_WITH_LIMIT),
+        }
+    }
+
+    fn weights(&self) -> Vec<f64> {
+        let fewest = self.lies.iter().flatten().min().copied().unwrap_or(0);
+        let ratio = self.ratio();
+        self.lies
+            .iter()
+            .map(|lies| match lies {
+                Some(lies) => ratio.powi((lies - fewest) as i32),
+                None => 0.0,
+            })
+            .collect()
+    }
+
+    // How many numbers can still be the secret.
+    pub fn candidates(&self) -> usize {
+        self.lies.iter().flatten().count()
+    }
+
+    // The next guess, and how sure the solver is that it is the secret.
+    pub fn next_guess(&self) -> (u32, f64) {
+        let weights = self.weights();
+        let total: f64 = weights.iter().sum();
+
+        let mut below = 0.0;
+        for (i, weight) in weights.iter().enumerate() {
+            if *weight > 0.0 && below + weight >= total / 2.0 {
+                return (self.first + i as u32, weight / total);
+            }
+            below += weight;
+        }
+
+        unreachable!("the secret is always among the candidates")
+    }
+
+    pub fn answer(&mut self, guess: u32, outcome: Outcome) {
+        let limit = match self.noise {
+            Noise::Limit(k) => Some(k),
+            Noise::Chance(_) => None,
+        };
+
+        for (i, lies) in self.lies.iter_mut().enumerate() {
+            let Some(count) = lies else { continue };
+            let candidate = self.first + i as u32;
+            let truth = match guess.cmp(&candidate) {
+                Ordering::Less => Outcome::Less,
+                Ordering::Greater => Outcome::Greater,
+                Ordering::Equal => Outcome::Won,
+            };
+
+            // A right guess is never denied and a wrong one never accepted.
+            if (truth == Outcome::Won) != (outcome == Outcome::Won) {
+                *lies = None;
+            } else if truth != outcome {
+                *count += 1;
+                if limit.is_some_and(|k| *count > k) {
+                    *lies = None;
+                }
+            }
+        }
+    }
+}
+
+// The fewest yes-or-no questions that can find one of `size` numbers in
+// spite of the lies, or `None` if no number of questions is enough.
+//
+// With at most k lies this is Berlekamp's volume bound, the worst case
+// against a host that picks the moments to lie: every number needs
+// its own set of answer sequences, those within k lies of the true ones, so
+// 2^q must be at least size * (C(q, 0) + C(q, 1) + ... + C(q, k)). When every
+// answer is a lie with probability p, each one tells at most 1 - H(p) bits,
+// the capacity of the binary symmetric channel, so about log2(size) / (1 -
+// H(p)) questions are needed.
+pub fn query_bound(size: u64, noise: Noise) -> Option<u32> {
+    let bits = (size as f64).log2();
+    match noise {
+        Noise::Chance(p) if p >= 0.5 => None,
+        Noise::Chance(p) if p <= 0.0 => Some(bits.ceil() as u32),
+        Noise::Chance(p) => {
+            let entropy = -p * p.log2() - (1.0 - p) * (1.0 - p).log2();
+            Some((bits / (1.0 - entropy)).ceil() as u32)
+        }
+        Noise::Limit(k) => {
+            // Every question more adds at most one bit to the volume, so once
+            // `q` questions are enough, more are too. That allows doubling `q`
+            // until it is enough and then searching back for the least.
+            // The small allowance keeps rounding from missing an exact fit,
+            // like 2^11 = 2 * (C(11, 0) + ... + C(11, 5)).
+            let enough = |q: u32| f64::from(q) + 1e-9 >= bits + log2_volume(q, k);
+            let (mut low, mut high) = (0, 1);
+            while !enough(high) {
+                low = high;
+                high = high.checked_mul(2)?;
+            }
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if enough(middle) {
+                    high = middle;
+                } else {
+                    low = middle + 1;
+                }
+            }
+            Some(high)
+        }
+    }
+}
+
+// log2 of C(q, 0) + C(q, 1) + ... + C(q, k). The binomials are added up as
+// logarithms, scaled by the largest, since for long games they are far
+// beyond what a float holds.
+fn log2_volume(q: u32, k: u32) -> f64 {
+    let mut ln_binomial = 0.0;
+    let terms: Vec<f64> = (0..=k.min(q))
+        .map(|j| {
+            if j > 0 {
+                ln_binomial += f64::from(q - j + 1).ln() - f64::from(j).ln();
+            }
+            ln_binomial
+        })
+        .collect();
+
+    let largest = terms.iter().copied().fold(f64::MIN, f64::max);
+    let sum: f64 = terms.iter().map(|term| (term - largest).exp()).sum();
+    (largest + sum.ln()) / std::f64::consts::LN_2
+}
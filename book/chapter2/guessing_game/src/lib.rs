@@ -0,0 +1,30 @@
+mod calendar;
+pub mod cli;
+pub mod config;
+pub mod contest;
+pub mod domain;
+pub mod game;
+pub mod grid;
+pub mod hint;
+pub mod http;
+pub mod input;
+pub mod interval;
+pub mod liar;
+pub mod mastermind;
+pub mod net;
+pub mod report;
+pub mod reverse;
+pub mod save;
+pub mod scores;
+pub mod simulate;
+pub mod strategy;
+pub mod timer;
+pub mod transcript;
+pub mod tui;
+pub mod turns;
+pub mod wordle;
+
+pub use config::Config;
+pub use game::{Game, Host, Outcome, State};
+pub use interval::Interval;
+pub use strategy::Strategy;
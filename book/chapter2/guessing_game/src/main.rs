@@ -1,37 +1,668 @@
-use std::io;
-use std::cmp::Ordering;
-use rand::Rng;
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use guessing_game::config::{Command, Difficulty, Format};
+use guessing_game::contest::{Contest, Standing};
+use guessing_game::domain::{self, Date, Dates, Domain, Floats, Integers, Kind, Letters, Round, Words};
+use guessing_game::game::fair_attempts;
+use guessing_game::grid::{self, Clues, Grid};
+use guessing_game::liar::{Noise, NoisyGame, Ulam};
+use guessing_game::mastermind::{self, Knuth, Mastermind, Rules, Symbols};
+use guessing_game::report::{Human, Json, Plain, Reporter};
+use guessing_game::save::{Autosave, SaveTarget, SavedGame};
+use guessing_game::scores::{self, Score};
+use guessing_game::strategy::{StrategyKind, MAX_GUESSES};
+use guessing_game::timer::{SystemClock, Timer};
+use guessing_game::wordle::{self, Style, Wordle};
+use guessing_game::transcript::{self, Header, RecordedInput, RecordedOutput, Recording, Transcript};
+use guessing_game::tui::Tui;
+use guessing_game::{cli, http, net, simulate, Config, Game, State};
 
 fn main() {
-    println!("Guessing the number!");
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(2);
+    });
+
+    match config.command {
+        Command::Play => play(&config),
+        Command::Simulate => simulate(&config),
+        Command::Scores => scores(&config),
+        Command::Serve => serve(&config),
+        Command::Connect => connect(&config),
+        Command::Api => api(&config),
+        Command::Replay => replay(&config),
+    }
+}
+
+// A new round as asked for on the command line, drawing the secret number
+// from `rng`. The attempt limit is left to the caller.
+fn new_game(config: &Config, rng: &mut StdRng) -> Game {
+    if config.evil {
+        Game::evil(config.range())
+    } else {
+        Game::from_rng(config.range(), rng)
+    }
+}
 
-    let secret_number: u32 = rand::thread_rng().gen_range(1..=100);
+fn with_attempt_limit(config: &Config, game: Game) -> Game {
+    match config.max_attempts() {
+        Some(max_attempts) => game.with_max_attempts(max_attempts),
+        None => game,
+    }
+}
 
-    
-    loop {
+fn play(config: &Config) {
+    // Without --seed a random one is picked, so that a recorded round can be
+    // played again exactly.
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
 
-        println!("Please input your guess:");
-        let mut guess = String::new();
+    // Guesses come from the script file if one is given, otherwise from
+    // stdin. Unless asked otherwise, anything but a person at a terminal gets
+    // the plain output.
+    let mut input: Box<dyn BufRead> = match &config.script {
+        Some(path) => {
+            let file = File::open(path).unwrap_or_else(|err| {
+                eprintln!("Failed to open {}: {err}", path.display());
+                process::exit(2);
+            });
+            Box::new(BufReader::new(file))
+        }
+        None => Box::new(io::stdin().lock()),
+    };
+    let scripted = config.script.is_some()
+        || (config.autoplay.is_none() && !io::stdin().is_terminal());
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read user input.");
+    // The modes of their own only print text for people, whoever reads it.
+    let format = config.format.unwrap_or(if scripted && config.mode().is_none() {
+        Format::Plain
+    } else {
+        Format::Human
+    });
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Please input number next time!");
-                continue;
-            },
+    // With --record everything read and printed also goes to the transcript.
+    let transcript = config.record.as_ref().map(|path| {
+        let header = Header {
+            args: args_without_record(),
+            seed,
+            format: format.to_string(),
         };
+        Transcript::create(path, &header).unwrap_or_else(|err| {
+            eprintln!("Failed to create {}: {err}", path.display());
+            process::exit(2);
+        })
+    });
+    if let Some(transcript) = &transcript {
+        input = Box::new(RecordedInput::new(input, Arc::clone(transcript)));
+    }
+    let stdout = || -> Box<dyn Write> {
+        match &transcript {
+            Some(transcript) => Box::new(RecordedOutput::new(io::stdout().lock(), Arc::clone(transcript))),
+            None => Box::new(io::stdout().lock()),
+        }
+    };
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small guess!"),
-            Ordering::Greater => println!("Too big guess!"),
-            Ordering::Equal => {
-                println!("You won!");
-                break;
+    let mut reporter: Box<dyn Reporter> = match format {
+        Format::Human => Box::new(Human::new(stdout())),
+        Format::Tui => {
+            let limit = config.time_limit.map(Duration::from_secs);
+            match &transcript {
+                Some(transcript) => Box::new(Tui::new(RecordedOutput::new(io::stdout(), Arc::clone(transcript)), limit)),
+                None => Box::new(Tui::new(io::stdout(), limit)),
             }
         }
+        Format::Plain => Box::new(Plain::new(stdout())),
+        Format::Json => Box::new(Json::new(stdout())),
+    };
+
+    if config.reverse {
+        return finish(cli::reverse(config.range(), input, stdout()));
+    }
+
+    if config.domain != Kind::Number {
+        return finish(play_in_domain(config, &mut rng, input, stdout()));
+    }
+
+    if config.wordle {
+        return finish(play_wordle(config, &mut rng, input, stdout()));
+    }
+
+    if config.mastermind {
+        return finish(play_mastermind(config, &mut rng, input, stdout()));
+    }
+
+    if let Some(size) = &config.grid {
+        return finish(play_grid(config, size, &mut rng, input, stdout()));
+    }
+
+    if let Some(noise) = config.lies.map(Noise::Limit).or(config.noise.map(Noise::Chance)) {
+        return finish(play_noisy(config, noise, &mut rng, input, stdout()));
+    }
+
+    if config.player_count.is_some() || !config.players.is_empty() {
+        let names = match config.player_count {
+            Some(count) => cli::ask_names(count, &mut input, stdout()),
+            None => Ok(config.players.clone()),
+        };
+
+        let result = names.and_then(|names| {
+            let mut contest = if config.race {
+                let games = names
+                    .iter()
+                    .map(|_| with_attempt_limit(config, new_game(config, &mut rng)))
+                    .collect();
+                Contest::race(names, games)
+            } else {
+                // The attempt limit counts the guesses of every player, not
+                // of the shared game.
+                Contest::hot_seat(names, new_game(config, &mut rng), config.max_attempts())
+            };
+            cli::contest(&mut contest, input, stdout())
+        });
+
+        match result {
+            Ok(Standing::NoWinner) => process::exit(1),
+            Ok(_) => return,
+            Err(e) => {
+                eprintln!("Failed to play the game: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    // A resumed round goes on with the time already played.
+    let (mut game, played) = match &config.resume {
+        Some(path) => resume(path),
+        None => (with_attempt_limit(config, new_game(config, &mut rng)), Duration::ZERO),
+    };
+
+    // Typing `save` or hitting Ctrl-C saves the round, to the file it was
+    // resumed from unless told otherwise.
+    let save = SaveTarget {
+        path: config
+            .save_file
+            .clone()
+            .or_else(|| config.resume.clone())
+            .unwrap_or_else(|| PathBuf::from("guessing_game.save")),
+        obfuscate: config.obfuscate,
+    };
+    if config.autoplay.is_none() {
+        let snapshot = Arc::new(Mutex::new(None));
+        save_on_interrupt(save.clone(), Arc::clone(&snapshot), played);
+        reporter = Box::new(Autosave::new(reporter, snapshot));
+    }
+
+    let clock = SystemClock::new();
+    let mut timer = Timer::new(&clock).with_elapsed(played);
+    if let Some(seconds) = config.time_limit {
+        timer = timer.with_limit(Duration::from_secs(seconds));
+    }
+
+    let result = match config.autoplay {
+        Some(kind) => {
+            let mut strategy = kind.build(&config.range(), Some(seed));
+            cli::autoplay(&mut game, strategy.as_mut(), reporter.as_mut())
+        }
+        None => cli::play(&mut game, input, reporter.as_mut(), &mut timer, Some(&save)),
+    };
+
+    if config.speedrun && result.is_ok() {
+        if let Err(e) = reporter.splits(timer.splits(), timer.elapsed()) {
+            eprintln!("Failed to write the splits: {e}");
+        }
+    }
+
+    // Only rounds won by a person at the terminal make it into the
+    // high-score table.
+    let by_person = !scripted && config.autoplay.is_none();
+    if by_person && matches!(result, Ok(State::Won)) {
+        // The time includes any played before the round was saved.
+        let score = Score::new(&config.player_name(), &game, timer.elapsed());
+        if let Some(file) = config.score_file() {
+            if let Err(e) = file.append(&score) {
+                eprintln!("Failed to save the score to {}: {e}", file.path().display());
+            }
+        }
+    }
+
+    finish(result);
+}
+
+// Exits the way the round ended: 1 for a loss or an error, 3 for giving up,
+// and normally for a win or a round saved for later.
+fn finish(result: io::Result<State>) {
+    let code = match result {
+        Ok(State::Won | State::Playing) => return,
+        Ok(State::Lost | State::OutOfTime) => 1,
+        Ok(_) => 3,
+        Err(e) => {
+            eprintln!("Failed to play the game: {e}");
+            1
+        }
+    };
+    process::exit(code);
+}
+
+// A round over the --domain asked for, between --from and --to or the
+// default bounds of the domain.
+fn play_in_domain<R: BufRead, W: Write>(config: &Config, rng: &mut StdRng, input: R, output: W) -> io::Result<State> {
+    match config.domain {
+        Kind::Number => unreachable!("numbers are played by the classic game"),
+        Kind::Integer => {
+            let (min, max) = or_exit(bounds(config, -100, 100));
+            play_round(config, or_exit(Integers::new(min, max)), rng, input, output)
+        }
+        Kind::Float => {
+            let (min, max) = or_exit(bounds(config, 0.0, 1.0));
+            let epsilon = config.epsilon.unwrap_or(0.01);
+            play_round(config, or_exit(Floats::new(min, max, epsilon)), rng, input, output)
+        }
+        Kind::Letter => {
+            let (first, last) = or_exit(bounds(config, 'a', 'z'));
+            play_round(config, or_exit(Letters::new(first, last)), rng, input, output)
+        }
+        Kind::Word => {
+            play_round(config, or_exit(Words::from_list(&word_list(config))), rng, input, output)
+        }
+        Kind::Date => {
+            let first = Date::from_str("1900-01-01").expect("a valid date");
+            let last = Date::from_str("2099-12-31").expect("a valid date");
+            let (first, last) = or_exit(bounds(config, first, last));
+            play_round(config, or_exit(Dates::new(first, last)), rng, input, output)
+        }
+    }
+}
+
+fn play_round<D: Domain, R: BufRead, W: Write>(
+    config: &Config,
+    domain: D,
+    rng: &mut StdRng,
+    input: R,
+    output: W,
+) -> io::Result<State> {
+    let round = if config.evil {
+        Round::evil(domain)
+    } else {
+        Round::from_rng(domain, rng)
+    };
+
+    let max_attempts = if config.fair {
+        Some(fair_attempts(round.game().range()))
+    } else {
+        config.max_attempts
+    };
+    let mut round = match max_attempts {
+        Some(max_attempts) => round.with_max_attempts(max_attempts),
+        None => round,
+    };
+
+    cli::play_domain(&mut round, input, output)
+}
+
+// A round of Wordle with a word from --words or the bundled list. Colors are
+// for people at a terminal, everyone else gets symbols.
+fn play_wordle<R: BufRead, W: Write>(config: &Config, rng: &mut StdRng, input: R, output: W) -> io::Result<State> {
+    let words = Words::from_list(&word_list(config)).unwrap_or_else(|err| {
+        eprintln!("Problem with the word list: {err}");
+        process::exit(2);
+    });
+
+    let mut game = Wordle::from_rng(words, rng)
+        .with_max_attempts(config.max_attempts.unwrap_or(wordle::DEFAULT_ATTEMPTS));
+    if config.hard {
+        game = game.with_hard_mode();
+    }
+
+    // A recorded round is played again with its output piped, which gets the
+    // symbols, so it has to be recorded with them too.
+    let style = if config.symbols || config.record.is_some() || !io::stdout().is_terminal() {
+        Style::Symbols
+    } else {
+        Style::Color
+    };
+    cli::wordle(&mut game, input, output, style)
+}
+
+// The solver keeps every code in memory, so it only takes on this many.
+const MAX_SOLVER_CODES: u64 = 1_000_000;
+
+// A round of code breaking, classic Mastermind unless told otherwise: codes
+// of 4 symbols out of 6, with repeats.
+fn play_mastermind<R: BufRead, W: Write>(config: &Config, rng: &mut StdRng, input: R, output: W) -> io::Result<State> {
+    let symbols = if config.colors { Symbols::Colors } else { Symbols::Digits };
+    let rules = Rules::new(
+        config.length.unwrap_or(4),
+        config.alphabet.unwrap_or(6),
+        !config.no_repeats,
+        symbols,
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("Problem with the code: {err}");
+        process::exit(2);
+    });
+
+    let mut game = Mastermind::from_rng(rules, rng)
+        .with_max_attempts(config.max_attempts.unwrap_or(mastermind::DEFAULT_ATTEMPTS));
+
+    if config.solve {
+        if rules.count().is_none_or(|count| count > MAX_SOLVER_CODES) {
+            eprintln!("The solver takes on at most {MAX_SOLVER_CODES} codes, please use a shorter code or a smaller alphabet");
+            process::exit(2);
+        }
+        cli::mastermind_solve(&mut game, &mut Knuth::new(rules), output)
+    } else {
+        cli::mastermind(&mut game, input, output)
+    }
+}
+
+// A round of finding a point on a grid, with directions unless --warmth
+// asks for hotter and colder.
+fn play_grid<R: BufRead, W: Write>(config: &Config, size: &grid::Size, rng: &mut StdRng, input: R, output: W) -> io::Result<State> {
+    let clues = if config.warmth { Clues::Warmth } else { Clues::Directions };
+    let mut game = Grid::from_rng(size.clone(), clues, rng);
+
+    let max_attempts = match config.max_attempts {
+        Some(max_attempts) => Some(max_attempts),
+        None if config.fair => Some(grid::fair_grid_attempts(size)),
+        None => None,
+    };
+    if let Some(max_attempts) = max_attempts {
+        game = game.with_max_attempts(max_attempts);
+    }
+
+    cli::grid(&mut game, input, output)
+}
+
+// The solver for a lying host keeps a count for every number, so it only
+// takes on ranges this big.
+const MAX_SOLVER_NUMBERS: u64 = 1_000_000;
+
+// A round against a host that lies up to --lies times, or with a chance of
+// --noise for every answer.
+fn play_noisy<R: BufRead, W: Write>(config: &Config, noise: Noise, rng: &mut StdRng, input: R, output: W) -> io::Result<State> {
+    let range = config.range();
+    let mut game = NoisyGame::from_rng(range.clone(), noise, rng);
+    if let Some(max_attempts) = config.max_attempts {
+        game = game.with_max_attempts(max_attempts);
+    }
+
+    if config.solve {
+        let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+        if size > MAX_SOLVER_NUMBERS {
+            eprintln!("The solver takes on at most {MAX_SOLVER_NUMBERS} numbers, please use a smaller range");
+            process::exit(2);
+        }
+        cli::noisy_solve(&mut game, &mut Ulam::new(&range, noise), output)
+    } else {
+        cli::noisy(&mut game, input, output)
+    }
+}
+
+// The words from --words, or the bundled ones.
+fn word_list(config: &Config) -> String {
+    match &config.words {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("Failed to read {}: {err}", path.display());
+            process::exit(2);
+        }),
+        None => domain::WORDS.to_string(),
+    }
+}
+
+// --from and --to read as values of the domain, or the defaults.
+fn bounds<T>(config: &Config, first: T, last: T) -> Result<(T, T), String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let parse = |flag: &str, value: &Option<String>, default: T| match value {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("invalid value '{value}' for {flag}: {e}")),
+        None => Ok(default),
+    };
+    Ok((parse("--from", &config.from, first)?, parse("--to", &config.to, last)?))
+}
+
+fn or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("Problem with --domain: {err}");
+        process::exit(2);
+    })
+}
+
+// The command line the round was started with, without the program name
+// and without --record, so that playing it again does not overwrite the
+// transcript.
+fn args_without_record() -> Vec<String> {
+    let mut args = env::args().skip(1);
+    let mut kept = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--record" {
+            args.next();
+        } else {
+            kept.push(arg);
+        }
+    }
+    kept
+}
+
+// The saved round, and how long it had been played.
+fn resume(path: &Path) -> (Game, Duration) {
+    let saved = SavedGame::load(path).unwrap_or_else(|err| {
+        eprintln!("Failed to read {}: {err}", path.display());
+        process::exit(2);
+    });
+
+    let game = saved.restore().unwrap_or_else(|err| {
+        eprintln!("Failed to resume {}: {err}", path.display());
+        process::exit(2);
+    });
+    (game, saved.elapsed())
+}
+
+// Saves the latest snapshot of the round to `save` when the player hits
+// Ctrl-C, and then quits. The time played is `played` plus the time since the
+// handler was set, right before the round starts.
+fn save_on_interrupt(save: SaveTarget, snapshot: Arc<Mutex<Option<Game>>>, played: Duration) {
+    let started = Instant::now();
+    let result = ctrlc::set_handler(move || {
+        if let Some(game) = snapshot.lock().unwrap().as_ref() {
+            match save.save(game, played + started.elapsed()) {
+                Ok(()) => eprintln!("\nGame saved, continue with --resume {}", save.path.display()),
+                Err(e) => eprintln!("\nFailed to save the game: {e}"),
+            }
+        }
+        process::exit(130);
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to catch Ctrl-C, the game will not be saved on exit: {e}");
+    }
+}
+
+fn simulate(config: &Config) {
+    let range = config.range();
+    let games = config.games.unwrap_or(1000);
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let strategies = if config.strategies.is_empty() {
+        vec![StrategyKind::BinarySearch, StrategyKind::Random, StrategyKind::LinearScan]
+    } else {
+        config.strategies.clone()
+    };
+
+    let summaries: Vec<_> = strategies
+        .into_iter()
+        .map(|kind| simulate::simulate(kind, &range, games, seed, config.max_attempts()))
+        .collect();
+
+    let stdout = io::stdout().lock();
+    let result = if config.csv {
+        simulate::write_csv(&summaries, stdout)
+    } else {
+        println!(
+            "Range {}-{}, seed {seed}, attempt limit {}",
+            range.start(),
+            range.end(),
+            config
+                .max_attempts()
+                .map_or(format!("none, at most {MAX_GUESSES} guesses"), |max| max.to_string())
+        );
+        simulate::write_text(&summaries, stdout)
+    };
+
+    if let Err(e) = result {
+        eprintln!("Failed to write the results: {e}");
+        process::exit(1);
+    }
+}
+
+fn scores(config: &Config) {
+    let file = config.score_file().unwrap_or_else(|| {
+        eprintln!("No place to keep the scores, please use --scores <file>");
+        process::exit(2);
+    });
+
+    let loaded = file.load().unwrap_or_else(|err| {
+        eprintln!("Failed to read {}: {err}", file.path().display());
+        process::exit(1);
+    });
+    if loaded.skipped > 0 {
+        eprintln!(
+            "Skipped {} unreadable lines in {}",
+            loaded.skipped,
+            file.path().display()
+        );
+    }
+    if loaded.scores.is_empty() {
+        println!("No high scores yet.");
+        return;
+    }
+
+    for (table, best) in scores::best_by_table(&loaded.scores, config.top.unwrap_or(10)) {
+        let (min, max) = (table.min, table.max);
+        let mut title = match Difficulty::for_range(&(min..=max)) {
+            Some(difficulty) => format!("{difficulty} ({min}-{max})"),
+            None => format!("custom ({min}-{max})"),
+        };
+        if table.evil {
+            title.push_str(", evil host");
+        }
+        if table.hints {
+            title.push_str(", with hints");
+        }
+        println!("{title}");
+
+        for (place, score) in best.iter().enumerate() {
+            println!(
+                "{:>4}. {:<16} {:>4} attempts {:>9.1}s  {}",
+                place + 1,
+                score.name,
+                score.attempts,
+                score.elapsed_ms as f64 / 1000.0,
+                score.day()
+            );
+        }
+        println!();
+    }
+}
+
+fn serve(config: &Config) {
+    let port = config.port.unwrap_or(7878);
+    let listener = TcpListener::bind(("0.0.0.0", port)).unwrap_or_else(|err| {
+        eprintln!("Failed to listen on port {port}: {err}");
+        process::exit(1);
+    });
+    println!("Listening on port {port}");
+
+    // Connection `n` plays the round drawn from `seed + n`, so a seeded
+    // server hands out the same rounds every time it is started.
+    let base_seed = config.seed;
+    let game_config = config.clone();
+    let new_game = move |number: u64| {
+        let mut rng = match base_seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(number)),
+            None => StdRng::from_entropy(),
+        };
+        with_attempt_limit(&game_config, new_game(&game_config, &mut rng))
+    };
+
+    let idle_timeout = Duration::from_secs(config.idle_timeout.unwrap_or(60));
+    if let Err(e) = net::serve(listener, new_game, idle_timeout) {
+        eprintln!("The server stopped: {e}");
+        process::exit(1);
+    }
+}
+
+fn connect(config: &Config) {
+    let address = config.address.as_deref().unwrap_or("127.0.0.1:7878");
+    let stream = TcpStream::connect(address).unwrap_or_else(|err| {
+        eprintln!("Failed to connect to {address}: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = net::connect(stream, io::stdin().lock(), io::stdout().lock()) {
+        eprintln!("Failed to play the game: {e}");
+        process::exit(1);
+    }
+}
+
+fn api(config: &Config) {
+    let port = config.port.unwrap_or(8080);
+    let listener = TcpListener::bind(("0.0.0.0", port)).unwrap_or_else(|err| {
+        eprintln!("Failed to listen on port {port}: {err}");
+        process::exit(1);
+    });
+    println!("Serving the HTTP API on port {port}");
+
+    let expiry = Duration::from_secs(config.expiry.unwrap_or(600));
+    let store = Arc::new(http::Store::new(config.range(), expiry));
+    if let Err(e) = http::serve(listener, store, http::REQUEST_TIMEOUT) {
+        eprintln!("The server stopped: {e}");
+        process::exit(1);
+    }
+}
+
+fn replay(config: &Config) {
+    let path = config.transcript.as_deref().expect("checked by Config::build");
+    let recording = Recording::load(path).unwrap_or_else(|err| {
+        eprintln!("Failed to read {}: {err}", path.display());
+        process::exit(2);
+    });
+
+    if !config.verify {
+        if let Err(e) = recording.replay(config.speed.unwrap_or(1), io::stdout().lock()) {
+            eprintln!("Failed to replay {}: {e}", path.display());
+            process::exit(1);
+        }
+        return;
+    }
+
+    let output = env::current_exe()
+        .and_then(|program| recording.rerun(&program))
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to play {} again: {err}", path.display());
+            process::exit(1);
+        });
+
+    match transcript::first_difference(&recording.output(), &output) {
+        None => println!("{} plays back the same.", path.display()),
+        Some((line, expected, actual)) => {
+            println!("{} plays back differently, first at line {line}:", path.display());
+            println!("  recorded: {expected}");
+            println!("  now:      {actual}");
+            process::exit(1);
+        }
     }
 }
@@ -0,0 +1,374 @@
+use std::fmt;
+use rand::{Rng, RngCore};
+
+use crate::game::State;
+use crate::turns::Turns;
+
+// How many guesses a round has, unless told otherwise.
+pub const DEFAULT_ATTEMPTS: u32 = 10;
+
+// The most comparisons the solver makes for a guess. It looks at every
+// possible code for its next guess while that stays below the limit,
+// otherwise just at the codes still possible, or an evenly spread sample of
+// them.
+const FULL_SEARCH_LIMIT: usize = 5_000_000;
+
+// A guess is scored against at most this many of the codes still possible,
+// an evenly spread sample standing in for the rest.
+const SCORING_SAMPLE: usize = 2_000;
+
+// The symbols a code is made of: digits from 0, or colors written as their
+// first letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbols {
+    Digits,
+    Colors,
+}
+
+const DIGITS: &[u8] = b"0123456789";
+// Red, green, blue, yellow, orange, purple, white and black.
+const COLORS: &[u8] = b"RGBYOPWK";
+
+impl Symbols {
+    fn chars(self) -> &'static [u8] {
+        match self {
+            Symbols::Digits => DIGITS,
+            Symbols::Colors => COLORS,
+        }
+    }
+
+    pub fn max_alphabet(self) -> u8 {
+        self.chars().len() as u8
+    }
+}
+
+// What codes look like in a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub length: usize,
+    // How many different symbols there are.
+    pub alphabet: u8,
+    // Whether a symbol may be used more than once in a code.
+    pub repeats: bool,
+    pub symbols: Symbols,
+}
+
+impl Rules {
+    pub fn new(length: usize, alphabet: u8, repeats: bool, symbols: Symbols) -> Result<Rules, String> {
+        if length == 0 {
+            return Err("a code needs at least one symbol".to_string());
+        }
+        if !(2..=symbols.max_alphabet()).contains(&alphabet) {
+            return Err(format!(
+                "the alphabet has to have between 2 and {} symbols",
+                symbols.max_alphabet()
+            ));
+        }
+        if !repeats && length > usize::from(alphabet) {
+            return Err(format!(
+                "a code of {length} different symbols needs an alphabet of at least {length}"
+            ));
+        }
+        Ok(Rules {
+            length,
+            alphabet,
+            repeats,
+            symbols,
+        })
+    }
+
+    // How many codes there are, or `None` if it is too many to count.
+    pub fn count(&self) -> Option<u64> {
+        let alphabet = u64::from(self.alphabet);
+        if self.repeats {
+            alphabet.checked_pow(self.length as u32)
+        } else {
+            (0..self.length as u64).try_fold(1u64, |count, i| count.checked_mul(alphabet - i))
+        }
+    }
+
+    // Every code, in order.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let mut codes: Vec<Vec<u8>> = vec![Vec::with_capacity(self.length)];
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+            for code in &codes {
+                for symbol in 0..self.alphabet {
+                    if self.repeats || !code.contains(&symbol) {
+                        let mut next = code.clone();
+                        next.push(symbol);
+                        longer.push(next);
+                    }
+                }
+            }
+            codes = longer;
+        }
+        codes.into_iter().map(Code).collect()
+    }
+
+    pub fn random_code<R: RngCore + ?Sized>(&self, rng: &mut R) -> Code {
+        let mut code = Vec::with_capacity(self.length);
+        while code.len() < self.length {
+            let symbol = rng.gen_range(0..self.alphabet);
+            if self.repeats || !code.contains(&symbol) {
+                code.push(symbol);
+            }
+        }
+        Code(code)
+    }
+
+    // Reads a code like `0123` or `RGBY`. Spaces and commas between the
+    // symbols are left out, and colors are not case sensitive.
+    pub fn parse(&self, text: &str) -> Result<Code, Rejected> {
+        let chars = self.symbols.chars();
+        let mut code = Vec::new();
+        for c in text.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+            let symbol = chars[..usize::from(self.alphabet)]
+                .iter()
+                .position(|&allowed| char::from(allowed) == c.to_ascii_uppercase())
+                .ok_or(Rejected::UnknownSymbol(c))?;
+            code.push(symbol as u8);
+        }
+
+        if code.len() != self.length {
+            return Err(Rejected::WrongLength { expected: self.length });
+        }
+        if !self.repeats && (1..code.len()).any(|i| code[..i].contains(&code[i])) {
+            return Err(Rejected::Repeats);
+        }
+        Ok(Code(code))
+    }
+
+    // The symbols that can be used, e.g. `0-5` or `RGBYOP`.
+    pub fn alphabet_text(&self) -> String {
+        let chars = &self.symbols.chars()[..usize::from(self.alphabet)];
+        match self.symbols {
+            Symbols::Digits => format!("{}-{}", char::from(chars[0]), char::from(chars[chars.len() - 1])),
+            Symbols::Colors => String::from_utf8_lossy(chars).into_owned(),
+        }
+    }
+
+    pub fn display<'a>(&self, code: &'a Code) -> CodeDisplay<'a> {
+        CodeDisplay {
+            code,
+            symbols: self.symbols,
+        }
+    }
+}
+
+// A sequence of symbols, each a number below the size of the alphabet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Code(pub Vec<u8>);
+
+pub struct CodeDisplay<'a> {
+    code: &'a Code,
+    symbols: Symbols,
+}
+
+impl fmt::Display for CodeDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &symbol in &self.code.0 {
+            write!(f, "{}", char::from(self.symbols.chars()[usize::from(symbol)]))?;
+        }
+        Ok(())
+    }
+}
+
+// The answer to a guess: how many symbols are right and in the right place
+// (bulls, or black pegs), and how many more are right but in the wrong
+// place (cows, or white pegs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Feedback {
+    pub exact: u32,
+    pub partial: u32,
+}
+
+impl Feedback {
+    pub fn compare(guess: &Code, secret: &Code) -> Feedback {
+        let mut exact = 0;
+        // No alphabet has more symbols than there are digits.
+        let mut guess_counts = [0u32; DIGITS.len()];
+        let mut secret_counts = [0u32; DIGITS.len()];
+
+        for (&g, &s) in guess.0.iter().zip(&secret.0) {
+            if g == s {
+                exact += 1;
+            } else {
+                guess_counts[usize::from(g)] += 1;
+                secret_counts[usize::from(s)] += 1;
+            }
+        }
+        let partial = guess_counts
+            .iter()
+            .zip(&secret_counts)
+            .map(|(g, s)| g.min(s))
+            .sum();
+
+        Feedback { exact, partial }
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} exact, {} partial", self.exact, self.partial)
+    }
+}
+
+// Why a line typed is no code under the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejected {
+    WrongLength { expected: usize },
+    UnknownSymbol(char),
+    Repeats,
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejected::WrongLength { expected } => write!(f, "The code has {expected} symbols."),
+            Rejected::UnknownSymbol(c) => write!(f, "'{c}' is not one of the symbols."),
+            Rejected::Repeats => write!(f, "No symbol can be used twice."),
+        }
+    }
+}
+
+// A round of code breaking: the secret code is cracked once a guess has
+// every symbol exact.
+#[derive(Debug, Clone)]
+pub struct Mastermind {
+    rules: Rules,
+    secret: Code,
+    turns: Turns<Code, Feedback>,
+}
+
+impl Mastermind {
+    pub fn new(rules: Rules, secret: Code) -> Mastermind {
+        Mastermind {
+            rules,
+            secret,
+            turns: Turns::new(),
+        }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(rules: Rules, rng: &mut R) -> Mastermind {
+        let secret = rules.random_code(rng);
+        Mastermind::new(rules, secret)
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Mastermind {
+        self.turns = self.turns.with_limit(max_attempts);
+        self
+    }
+
+    pub fn guess(&mut self, guess: &Code) -> Feedback {
+        let feedback = Feedback::compare(guess, &self.secret);
+        let cracked = feedback.exact as usize == self.rules.length;
+        self.turns.record(guess.clone(), feedback, cracked);
+        feedback
+    }
+
+    pub fn give_up(&mut self) {
+        self.turns.end(State::GaveUp);
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn turns(&self) -> &Turns<Code, Feedback> {
+        &self.turns
+    }
+}
+
+// Knuth's minimax solver. Every guess is the one whose worst answer leaves
+// the fewest codes still possible, so classic Mastermind (4 symbols out of
+// 6, with repeats) is always solved in at most 5 guesses.
+pub struct Knuth {
+    rules: Rules,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Knuth {
+    pub fn new(rules: Rules) -> Knuth {
+        let all = rules.all_codes();
+        Knuth {
+            rules,
+            candidates: all.clone(),
+            all,
+        }
+    }
+
+    // How many codes are still possible.
+    pub fn candidates(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn next_guess(&self) -> Code {
+        // Working out the first guess takes the longest and gains nothing:
+        // Knuth opens with two symbols twice each, like 0011, and without
+        // repeats every first guess is as good as 0123.
+        if self.candidates.len() == self.all.len() {
+            let alphabet = usize::from(self.rules.alphabet);
+            let step = if self.rules.repeats { 2 } else { 1 };
+            return Code((0..self.rules.length).map(|i| (i / step % alphabet) as u8).collect());
+        }
+        if self.candidates.len() == 1 {
+            return self.candidates[0].clone();
+        }
+
+        // Scoring every guess against every candidate takes too long once
+        // the first answers leave many codes, so both are sampled then.
+        let scored = sample(&self.candidates, SCORING_SAMPLE);
+        let pool = if self.all.len() * scored.len() <= FULL_SEARCH_LIMIT {
+            sample(&self.all, self.all.len())
+        } else {
+            sample(&self.candidates, FULL_SEARCH_LIMIT / scored.len())
+        };
+
+        let length = self.rules.length;
+        let mut best: Option<(usize, bool, &Code)> = None;
+        for guess in pool {
+            // How many candidates give each answer, indexed by exact and
+            // partial matches.
+            let mut counts = vec![0usize; (length + 1) * (length + 1)];
+            for candidate in &scored {
+                let feedback = Feedback::compare(guess, candidate);
+                counts[feedback.exact as usize * (length + 1) + feedback.partial as usize] += 1;
+            }
+            let worst = counts.into_iter().max().unwrap_or(0);
+            let possible = self.candidates.binary_search(guess).is_ok();
+
+            // Ties go to a guess that could be the code, then to the first.
+            let better = match best {
+                None => true,
+                Some((best_worst, best_possible, _)) => {
+                    worst < best_worst || (worst == best_worst && possible && !best_possible)
+                }
+            };
+            if better {
+                best = Some((worst, possible, guess));
+            }
+        }
+
+        best.map(|(_, _, guess)| guess.clone())
+            .expect("there is always a code left to guess")
+    }
+
+    pub fn feedback(&mut self, guess: &Code, feedback: Feedback) {
+        self.candidates
+            .retain(|candidate| Feedback::compare(guess, candidate) == feedback);
+    }
+}
+
+// At most `count` of `codes`, spread evenly over all of them.
+fn sample(codes: &[Code], count: usize) -> Vec<&Code> {
+    if codes.len() <= count {
+        return codes.iter().collect();
+    }
+    (0..count).map(|i| &codes[i * codes.len() / count]).collect()
+}
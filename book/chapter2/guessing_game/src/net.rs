@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, Outcome, State};
+use crate::input::{self, Line};
+
+// The line protocol spoken over TCP. After connecting, the server greets the
+// client with `RANGE <min> <max>`, and then answers every request line:
+//
+//   GUESS <n>  ->  LESS | GREATER | WIN <attempts> | LOSE <secret>
+//   QUIT       ->  BYE <secret>
+//
+// `LESS` means the guess was less than the secret number. Lines the server
+// does not understand and guesses outside of the range are answered with
+// `ERROR <reason>`, and do not cost an attempt. A client that stays silent
+// for too long gets `TIMEOUT`. The connection is closed after `WIN`, `LOSE`,
+// `BYE` and `TIMEOUT`, and after a line longer than `MAX_LINE` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Request {
+    Guess(u32),
+    Quit,
+}
+
+impl Request {
+    pub fn parse(line: &str) -> Result<Request, String> {
+        let mut words = line.split_whitespace();
+        let request = match (words.next(), words.next(), words.next()) {
+            (Some("GUESS"), Some(number), None) => number
+                .parse()
+                .map(Request::Guess)
+                .map_err(|_| format!("'{number}' is not a number"))?,
+            (Some("QUIT"), None, None) => Request::Quit,
+            (None, _, _) => return Err("empty line".to_string()),
+            _ => return Err(format!("unknown request '{}'", line.trim())),
+        };
+        Ok(request)
+    }
+}
+
+// Answers a single request line, and tells whether the connection is over.
+pub fn respond(game: &mut Game, line: &str) -> (String, bool) {
+    match Request::parse(line) {
+        Ok(Request::Guess(guess)) if !game.range().contains(&guess) => {
+            let range = game.range();
+            (format!("ERROR {guess} is outside of {}-{}", range.start(), range.end()), false)
+        }
+        Ok(Request::Guess(guess)) => {
+            let outcome = game.guess(guess);
+            match (outcome, game.state()) {
+                (Outcome::Won, _) => (format!("WIN {}", game.attempts()), true),
+                (_, State::Lost) => (format!("LOSE {}", game.secret_number()), true),
+                (Outcome::Less, _) => ("LESS".to_string(), false),
+                (Outcome::Greater, _) => ("GREATER".to_string(), false),
+            }
+        }
+        Ok(Request::Quit) => {
+            game.give_up();
+            (format!("BYE {}", game.secret_number()), true)
+        }
+        Err(reason) => (format!("ERROR {reason}"), false),
+    }
+}
+
+// The longest request line the server reads, newline included. Anything
+// longer would only let a client fill up the server's memory.
+pub const MAX_LINE: u64 = 256;
+
+// Reads from a socket until a deadline, however many reads it takes. A
+// read timeout alone starts over with every byte, so a client sending one
+// byte at a time would never time out.
+pub struct Deadline {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Deadline {
+    pub fn new(stream: TcpStream, timeout: Duration) -> Deadline {
+        Deadline {
+            stream,
+            deadline: Instant::now() + timeout,
+        }
+    }
+
+    // Moves the deadline to `timeout` from now.
+    pub fn restart(&mut self, timeout: Duration) {
+        self.deadline = Instant::now() + timeout;
+    }
+}
+
+impl Read for Deadline {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+// Plays one game with the client on the other end of `stream`.
+pub fn handle(stream: TcpStream, mut game: Game, idle_timeout: Duration) -> io::Result<()> {
+    let mut reader = BufReader::new(Deadline::new(stream.try_clone()?, idle_timeout));
+    let mut writer = stream;
+
+    writeln!(
+        writer,
+        "RANGE {} {}",
+        game.range().start(),
+        game.range().end()
+    )?;
+
+    loop {
+        reader.get_mut().restart(idle_timeout);
+        let mut line = String::new();
+        match reader.by_ref().take(MAX_LINE).read_line(&mut line) {
+            Ok(0) => {
+                game.give_up();
+                return Ok(());
+            }
+            Ok(_) if !line.ends_with('\n') && line.len() as u64 == MAX_LINE => {
+                game.give_up();
+                return writeln!(writer, "ERROR the line is longer than {MAX_LINE} bytes");
+            }
+            Ok(_) => {}
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                game.give_up();
+                return writeln!(writer, "TIMEOUT");
+            }
+            // Not valid UTF-8.
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                writeln!(writer, "ERROR the line is not valid UTF-8")?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+
+        let (response, done) = respond(&mut game, &line);
+        writeln!(writer, "{response}")?;
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+// Accepts connections on `listener` forever, playing an independent game on
+// its own thread with each of them. `new_game` is given the number of the
+// connection, counting from 0, and sets up the game for it.
+pub fn serve<F>(listener: TcpListener, new_game: F, idle_timeout: Duration) -> io::Result<()>
+where
+    F: Fn(u64) -> Game + Send + Sync + 'static,
+{
+    let new_game = Arc::new(new_game);
+
+    for (number, stream) in (0..).zip(listener.incoming()) {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to accept a connection: {e}");
+                continue;
+            }
+        };
+
+        let new_game = Arc::clone(&new_game);
+        thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map_or("unknown".to_string(), |addr| addr.to_string());
+            if let Err(e) = handle(stream, new_game(number), idle_timeout) {
+                eprintln!("Connection {number} from {peer} failed: {e}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+// The client side: sends every guess read from `input` to the server at the
+// other end of `stream` and shows `output` the answers, until the game is
+// over or the input runs out.
+pub fn connect<R: BufRead, W: Write>(stream: TcpStream, mut input: R, mut output: W) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    // Guesses outside of the range are caught before they are sent.
+    let range = match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["RANGE", min, max] => {
+            writeln!(output, "Guessing the number between {min} and {max}!")?;
+            min.parse().unwrap_or(0)..=max.parse().unwrap_or(u32::MAX)
+        }
+        _ => {
+            writeln!(output, "Guessing the number!")?;
+            0..=u32::MAX
+        }
+    };
+
+    loop {
+        writeln!(output, "Please input your guess:")?;
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            writeln!(writer, "QUIT")?;
+        } else {
+            match input::parse(&guess, &range) {
+                Ok(Line::Guess(guess)) => writeln!(writer, "GUESS {guess}")?,
+                Ok(Line::Quit) => writeln!(writer, "QUIT")?,
+                Ok(_) => {
+                    writeln!(output, "Only guesses and 'quit' work over the network.")?;
+                    continue;
+                }
+                Err(error) => {
+                    writeln!(output, "{error}")?;
+                    continue;
+                }
+            }
+        }
+
+        let mut answer = String::new();
+        if reader.read_line(&mut answer)? == 0 {
+            writeln!(output, "The server hung up.")?;
+            return Ok(());
+        }
+
+        let words: Vec<&str> = answer.split_whitespace().collect();
+        match words[..] {
+            ["LESS"] => writeln!(output, "Too small guess!")?,
+            ["GREATER"] => writeln!(output, "Too big guess!")?,
+            ["WIN", attempts] => {
+                writeln!(output, "You won in {attempts} attempts!")?;
+                return Ok(());
+            }
+            ["LOSE", secret] => {
+                writeln!(output, "You lost! The secret number was {secret}.")?;
+                return Ok(());
+            }
+            ["BYE", secret] => {
+                writeln!(output, "Giving up? The secret number was {secret}.")?;
+                return Ok(());
+            }
+            ["TIMEOUT"] => {
+                writeln!(output, "The server got tired of waiting.")?;
+                return Ok(());
+            }
+            ["ERROR", ..] => writeln!(output, "The server did not understand: {}", answer.trim())?,
+            _ => writeln!(output, "The server said something strange: {}", answer.trim())?,
+        }
+    }
+}
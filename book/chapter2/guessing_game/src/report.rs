@@ -0,0 +1,459 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+use serde::Serialize;
+
+use crate::game::{Game, Outcome, State};
+use crate::hint::{Clue, NoHint, Points};
+use crate::input::{InputError, COMMANDS};
+
+// Everything that happens during a round is reported through this trait, so
+// the game loop does not have to care who is reading the output.
+pub trait Reporter {
+    fn start(&mut self, game: &Game) -> io::Result<()>;
+    fn prompt(&mut self, game: &Game) -> io::Result<()>;
+
+    // Called before every prompt when the round has a time limit.
+    fn time_left(&mut self, _left: Duration) -> io::Result<()> {
+        Ok(())
+    }
+
+    // Called with a guess the player did not type themselves, e.g. one made
+    // by a strategy, so that it still shows up in the transcript.
+    fn echo(&mut self, _guess: u32) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn invalid(&mut self, game: &Game, input: &str, error: &InputError) -> io::Result<()>;
+    fn guessed(&mut self, game: &Game, guess: u32, outcome: Outcome) -> io::Result<()>;
+
+    // The answers to the `hint`, `history` and `help` commands.
+    fn hint(&mut self, game: &Game, clue: &Clue) -> io::Result<()>;
+    fn no_hint(&mut self, game: &Game, reason: NoHint) -> io::Result<()>;
+    fn history(&mut self, game: &Game) -> io::Result<()>;
+    fn help(&mut self) -> io::Result<()>;
+
+    // Called when the round has been saved to `path` to be resumed later.
+    fn saved(&mut self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()>;
+
+    // Called after the round in speed-run mode, with the time of every guess
+    // counted from the first prompt.
+    fn splits(&mut self, _splits: &[Duration], _total: Duration) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// The friendly, chatty output for a person at the terminal.
+pub struct Human<W: Write> {
+    output: W,
+}
+
+impl<W: Write> Human<W> {
+    pub fn new(output: W) -> Human<W> {
+        Human { output }
+    }
+}
+
+impl<W: Write> Reporter for Human<W> {
+    fn start(&mut self, _game: &Game) -> io::Result<()> {
+        writeln!(self.output, "Guessing the number!")
+    }
+
+    fn prompt(&mut self, game: &Game) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "Please input your guess ({}-{}):",
+            game.range().start(),
+            game.range().end()
+        )
+    }
+
+    fn time_left(&mut self, left: Duration) -> io::Result<()> {
+        writeln!(self.output, "{:.1} seconds left.", left.as_secs_f64())
+    }
+
+    fn echo(&mut self, guess: u32) -> io::Result<()> {
+        writeln!(self.output, "{guess}")
+    }
+
+    fn invalid(&mut self, _game: &Game, _input: &str, error: &InputError) -> io::Result<()> {
+        writeln!(self.output, "{error}")
+    }
+
+    fn guessed(&mut self, game: &Game, _guess: u32, outcome: Outcome) -> io::Result<()> {
+        match outcome {
+            Outcome::Less => write!(self.output, "Too small guess!")?,
+            Outcome::Greater => write!(self.output, "Too big guess!")?,
+            Outcome::Won => return Ok(()),
+        }
+
+        match game.attempts_remaining() {
+            Some(1) => writeln!(self.output, " 1 attempt left."),
+            Some(n) => writeln!(self.output, " {n} attempts left."),
+            None => writeln!(self.output),
+        }
+    }
+
+    fn hint(&mut self, game: &Game, clue: &Clue) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "{clue} (-{} points, {} left)",
+            clue.hint().cost(),
+            points_left(&game.points())
+        )
+    }
+
+    fn no_hint(&mut self, _game: &Game, reason: NoHint) -> io::Result<()> {
+        writeln!(self.output, "{reason}")
+    }
+
+    fn history(&mut self, game: &Game) -> io::Result<()> {
+        if game.history().is_empty() {
+            return writeln!(self.output, "No guesses yet.");
+        }
+        for (i, (guess, outcome)) in game.history().iter().enumerate() {
+            let answer = match outcome {
+                Outcome::Less => "too small",
+                Outcome::Greater => "too big",
+                Outcome::Won => "correct",
+            };
+            writeln!(self.output, "{:>4}. {guess} was {answer}", i + 1)?;
+        }
+        Ok(())
+    }
+
+    fn help(&mut self) -> io::Result<()> {
+        writeln!(self.output, "Type a number to guess it, or one of these commands:")?;
+        for (command, description) in COMMANDS {
+            writeln!(self.output, "  {command:<8} {description}")?;
+        }
+        Ok(())
+    }
+
+    fn saved(&mut self, path: &Path) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "Game saved, continue with --resume {}",
+            path.display()
+        )
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()> {
+        let secret_number = game.secret_number();
+        match game.state() {
+            State::Won => writeln!(self.output, "You won!"),
+            State::Lost => writeln!(self.output, "You lost! The secret number was {secret_number}."),
+            State::GaveUp => writeln!(self.output, "Giving up? The secret number was {secret_number}."),
+            State::OutOfTime => writeln!(self.output, "Time is up! The secret number was {secret_number}."),
+            State::Playing => return Ok(()),
+        }?;
+        writeln!(self.output, "Score: {}", game.points())
+    }
+
+    fn splits(&mut self, splits: &[Duration], total: Duration) -> io::Result<()> {
+        let mut previous = Duration::ZERO;
+        for (i, &split) in splits.iter().enumerate() {
+            writeln!(
+                self.output,
+                "Guess {} at {:.2}s (+{:.2}s)",
+                i + 1,
+                split.as_secs_f64(),
+                (split - previous).as_secs_f64()
+            )?;
+            previous = split;
+        }
+        writeln!(self.output, "Total time: {:.2}s", total.as_secs_f64())
+    }
+}
+
+// One `key=value` line per event, without any prompts, for scripts and CI.
+pub struct Plain<W: Write> {
+    output: W,
+}
+
+impl<W: Write> Plain<W> {
+    pub fn new(output: W) -> Plain<W> {
+        Plain { output }
+    }
+}
+
+impl<W: Write> Reporter for Plain<W> {
+    fn start(&mut self, game: &Game) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "start min={} max={}",
+            game.range().start(),
+            game.range().end()
+        )
+    }
+
+    fn prompt(&mut self, _game: &Game) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn invalid(&mut self, _game: &Game, input: &str, error: &InputError) -> io::Result<()> {
+        writeln!(self.output, "invalid input={input:?} reason={}", error.name())
+    }
+
+    fn guessed(&mut self, game: &Game, guess: u32, outcome: Outcome) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "guess={guess} result={} attempt={}",
+            outcome_name(outcome),
+            game.attempts()
+        )
+    }
+
+    fn hint(&mut self, _game: &Game, clue: &Clue) -> io::Result<()> {
+        write!(self.output, "hint={} cost={}", clue.hint(), clue.hint().cost())?;
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(clue) {
+            for (key, value) in fields {
+                write!(self.output, " {key}={value}")?;
+            }
+        }
+        writeln!(self.output)
+    }
+
+    fn no_hint(&mut self, _game: &Game, reason: NoHint) -> io::Result<()> {
+        writeln!(self.output, "no_hint reason={}", reason.name())
+    }
+
+    fn history(&mut self, game: &Game) -> io::Result<()> {
+        let guesses: Vec<String> = game
+            .history()
+            .iter()
+            .map(|&(guess, outcome)| format!("{guess}:{}", outcome_name(outcome)))
+            .collect();
+        writeln!(self.output, "history guesses={}", guesses.join(","))
+    }
+
+    fn help(&mut self) -> io::Result<()> {
+        let commands: Vec<&str> = COMMANDS.iter().map(|(command, _)| *command).collect();
+        writeln!(self.output, "help commands={}", commands.join(","))
+    }
+
+    fn saved(&mut self, path: &Path) -> io::Result<()> {
+        writeln!(self.output, "saved path={:?}", path.display().to_string())
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "end={} attempts={} secret={} points={}",
+            state_name(game.state()),
+            game.attempts(),
+            game.secret_number(),
+            game.points().total()
+        )
+    }
+
+    fn splits(&mut self, splits: &[Duration], total: Duration) -> io::Result<()> {
+        for (i, split) in splits.iter().enumerate() {
+            writeln!(self.output, "split attempt={} at_ms={}", i + 1, split.as_millis())?;
+        }
+        writeln!(self.output, "total_ms={}", total.as_millis())
+    }
+}
+
+// One JSON object per line, for bots that would rather not parse English.
+pub struct Json<W: Write> {
+    output: W,
+}
+
+impl<W: Write> Json<W> {
+    pub fn new(output: W) -> Json<W> {
+        Json { output }
+    }
+
+    fn write<T: Serialize>(&mut self, value: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.output, value)?;
+        writeln!(self.output)
+    }
+}
+
+#[derive(Serialize)]
+struct StartEvent {
+    start: Bounds,
+}
+
+#[derive(Serialize)]
+struct Bounds {
+    min: u32,
+    max: u32,
+    max_attempts: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct InvalidEvent<'a> {
+    invalid: &'a str,
+    reason: &'static str,
+}
+
+#[derive(Serialize)]
+struct HintEvent<'a> {
+    hint: String,
+    cost: u32,
+    clue: &'a Clue,
+}
+
+#[derive(Serialize)]
+struct NoHintEvent {
+    no_hint: &'static str,
+}
+
+#[derive(Serialize)]
+struct HistoryEvent {
+    history: Vec<HistoryEntry>,
+}
+
+#[derive(Serialize)]
+struct HistoryEntry {
+    guess: u32,
+    result: &'static str,
+}
+
+#[derive(Serialize)]
+struct HelpEvent {
+    help: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct GuessEvent {
+    guess: u32,
+    result: &'static str,
+    attempt: u32,
+}
+
+#[derive(Serialize)]
+struct SavedEvent<'a> {
+    saved: &'a Path,
+}
+
+#[derive(Serialize)]
+struct SplitsEvent {
+    splits_ms: Vec<u128>,
+    total_ms: u128,
+}
+
+#[derive(Serialize)]
+struct EndEvent {
+    end: &'static str,
+    attempts: u32,
+    secret: u32,
+    points: u32,
+}
+
+impl<W: Write> Reporter for Json<W> {
+    fn start(&mut self, game: &Game) -> io::Result<()> {
+        self.write(&StartEvent {
+            start: Bounds {
+                min: *game.range().start(),
+                max: *game.range().end(),
+                max_attempts: game.max_attempts(),
+            },
+        })
+    }
+
+    fn prompt(&mut self, _game: &Game) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn invalid(&mut self, _game: &Game, input: &str, error: &InputError) -> io::Result<()> {
+        self.write(&InvalidEvent {
+            invalid: input,
+            reason: error.name(),
+        })
+    }
+
+    fn guessed(&mut self, game: &Game, guess: u32, outcome: Outcome) -> io::Result<()> {
+        self.write(&GuessEvent {
+            guess,
+            result: outcome_name(outcome),
+            attempt: game.attempts(),
+        })
+    }
+
+    fn hint(&mut self, _game: &Game, clue: &Clue) -> io::Result<()> {
+        self.write(&HintEvent {
+            hint: clue.hint().to_string(),
+            cost: clue.hint().cost(),
+            clue,
+        })
+    }
+
+    fn no_hint(&mut self, _game: &Game, reason: NoHint) -> io::Result<()> {
+        self.write(&NoHintEvent {
+            no_hint: reason.name(),
+        })
+    }
+
+    fn history(&mut self, game: &Game) -> io::Result<()> {
+        self.write(&HistoryEvent {
+            history: game
+                .history()
+                .iter()
+                .map(|&(guess, outcome)| HistoryEntry {
+                    guess,
+                    result: outcome_name(outcome),
+                })
+                .collect(),
+        })
+    }
+
+    fn help(&mut self) -> io::Result<()> {
+        self.write(&HelpEvent {
+            help: COMMANDS.iter().map(|(command, _)| *command).collect(),
+        })
+    }
+
+    fn saved(&mut self, path: &Path) -> io::Result<()> {
+        self.write(&SavedEvent { saved: path })
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()> {
+        self.write(&EndEvent {
+            end: state_name(game.state()),
+            attempts: game.attempts(),
+            secret: game.secret_number(),
+            points: game.points().total(),
+        })
+    }
+
+    fn splits(&mut self, splits: &[Duration], total: Duration) -> io::Result<()> {
+        self.write(&SplitsEvent {
+            splits_ms: splits.iter().map(Duration::as_millis).collect(),
+            total_ms: total.as_millis(),
+        })
+    }
+}
+
+// The points the round would score if it were won right now.
+fn points_left(points: &Points) -> u32 {
+    Points {
+        won: true,
+        ..points.clone()
+    }
+    .total()
+}
+
+pub fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Less => "less",
+        Outcome::Greater => "greater",
+        Outcome::Won => "won",
+    }
+}
+
+pub fn state_name(state: State) -> &'static str {
+    match state {
+        State::Playing => "playing",
+        State::Won => "won",
+        State::Lost => "lost",
+        State::GaveUp => "gave_up",
+        State::OutOfTime => "out_of_time",
+    }
+}
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::interval::Interval;
+use crate::strategy::{BinarySearch, Strategy};
+
+// What the player says about the computer's guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    // The answer as `guess.cmp(&secret_number)`, which is what strategies and
+    // intervals work with. "Higher" means the guess was too small.
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Answer::Higher => Ordering::Less,
+            Answer::Lower => Ordering::Greater,
+            Answer::Correct => Ordering::Equal,
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Answer, String> {
+        match s.trim().to_lowercase().as_str() {
+            "higher" | "h" => Ok(Answer::Higher),
+            "lower" | "l" => Ok(Answer::Lower),
+            "correct" | "c" => Ok(Answer::Correct),
+            _ => Err(format!("unknown answer '{s}'")),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Answer::Higher => "higher",
+            Answer::Lower => "lower",
+            Answer::Correct => "correct",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Why an answer can not be true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contradiction {
+    // No number in the range fits the answer.
+    Range,
+    // The answer and the earlier answer at this index of `answers()` can not
+    // both be true.
+    Answer(usize),
+}
+
+// The reverse game, where the player has picked a number and the computer
+// guesses it with binary search.
+pub struct Reverse {
+    range: RangeInclusive<u32>,
+    strategy: BinarySearch,
+    candidates: Interval,
+    answers: Vec<(u32, Answer)>,
+}
+
+impl Reverse {
+    pub fn new(range: RangeInclusive<u32>) -> Reverse {
+        Reverse {
+            strategy: BinarySearch::new(&range),
+            candidates: Interval::new(&range),
+            range,
+            answers: Vec::new(),
+        }
+    }
+
+    pub fn next_guess(&mut self) -> u32 {
+        self.strategy.next_guess()
+    }
+
+    // Takes the player's answer to `guess`, unless it contradicts what they
+    // have said before, in which case nothing changes.
+    pub fn answer(&mut self, guess: u32, answer: Answer) -> Result<(), Contradiction> {
+        let mut candidates = self.candidates;
+        candidates.narrow(guess, answer.ordering());
+
+        if candidates.is_empty() {
+            return Err(self.contradiction(guess, answer));
+        }
+
+        self.candidates = candidates;
+        self.answers.push((guess, answer));
+        self.strategy.feedback(guess, answer.ordering());
+        Ok(())
+    }
+
+    // Every answer only allows an interval of numbers, and a set of intervals
+    // has nothing in common exactly when two of them do not overlap. So
+    // there always is a single answer, or the range, to blame.
+    fn contradiction(&self, guess: u32, answer: Answer) -> Contradiction {
+        let mut alone = Interval::new(&self.range);
+        alone.narrow(guess, answer.ordering());
+        if alone.is_empty() {
+            return Contradiction::Range;
+        }
+
+        let index = (0..self.answers.len())
+            .rev()
+            .find(|&i| {
+                let (earlier_guess, earlier_answer) = self.answers[i];
+                let mut both = alone;
+                both.narrow(earlier_guess, earlier_answer.ordering());
+                both.is_empty()
+            })
+            .expect("the answers so far are consistent with each other");
+
+        Contradiction::Answer(index)
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn candidates(&self) -> Interval {
+        self.candidates
+    }
+
+    pub fn answers(&self) -> &[(u32, Answer)] {
+        &self.answers
+    }
+}
@@ -0,0 +1,209 @@
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Game, Host, Outcome};
+use crate::hint::{Clue, Hint, NoHint};
+use crate::input::InputError;
+use crate::report::Reporter;
+
+// Not a secret in any real sense, just enough that the number can not be
+// read off the save file at a glance.
+const OBFUSCATION_KEY: u32 = 0x9e37_79b9;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Secret {
+    Plain(u32),
+    // The secret number XOR-ed with a fixed key, in hexadecimal.
+    Obfuscated(String),
+    Evil,
+}
+
+// A round in progress, as written to a save file. Only the guesses and hints
+// are kept; restoring asks for them again, which brings back the exact same
+// state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub min: u32,
+    pub max: u32,
+    pub secret: Secret,
+    pub max_attempts: Option<u32>,
+    pub guesses: Vec<u32>,
+    // Missing from files saved before there were hints.
+    #[serde(default)]
+    pub hints: Vec<Hint>,
+    // How long the round had been played, in milliseconds, so that a time
+    // limit goes on where it left off. Missing from older files.
+    #[serde(default)]
+    pub elapsed_ms: u64,
+}
+
+impl SavedGame {
+    pub fn from_game(game: &Game, obfuscate: bool, elapsed: Duration) -> SavedGame {
+        let secret = match game.host() {
+            Host::Honest(secret_number) if obfuscate => {
+                Secret::Obfuscated(format!("{:08x}", secret_number ^ OBFUSCATION_KEY))
+            }
+            Host::Honest(secret_number) => Secret::Plain(secret_number),
+            Host::Evil => Secret::Evil,
+        };
+
+        SavedGame {
+            min: *game.range().start(),
+            max: *game.range().end(),
+            secret,
+            max_attempts: game.max_attempts(),
+            guesses: game.history().iter().map(|&(guess, _)| guess).collect(),
+            hints: game.clues().iter().map(Clue::hint).collect(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    pub fn restore(&self) -> Result<Game, String> {
+        if self.min > self.max {
+            return Err(format!("the range {}-{} is empty", self.min, self.max));
+        }
+        let range = self.min..=self.max;
+
+        let mut game = match &self.secret {
+            Secret::Plain(secret_number) => new_game(range, *secret_number)?,
+            Secret::Obfuscated(hex) => {
+                let obfuscated = u32::from_str_radix(hex, 16)
+                    .map_err(|_| format!("'{hex}' is not an obfuscated secret"))?;
+                new_game(range, obfuscated ^ OBFUSCATION_KEY)?
+            }
+            Secret::Evil => Game::evil(range),
+        };
+        if let Some(max_attempts) = self.max_attempts {
+            game = game.with_max_attempts(max_attempts);
+        }
+
+        // Hints can only be had while playing, so they go first.
+        for &hint in &self.hints {
+            game.hint(Some(hint))
+                .map_err(|reason| format!("the {hint} hint can not be given: {reason}"))?;
+        }
+        for &guess in &self.guesses {
+            game.guess(guess);
+        }
+
+        Ok(game)
+    }
+
+    pub fn load(path: &Path) -> io::Result<SavedGame> {
+        let contents = fs::read(path)?;
+        serde_json::from_slice(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // Writes to a temporary file first and then moves it into place, so an
+    // interrupted save never leaves half a file behind.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        fs::write(&temporary, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&temporary, path)
+    }
+}
+
+fn new_game(range: RangeInclusive<u32>, secret_number: u32) -> Result<Game, String> {
+    if range.contains(&secret_number) {
+        Ok(Game::new(range, secret_number))
+    } else {
+        Err(format!("the secret number is outside of {}-{}", range.start(), range.end()))
+    }
+}
+
+// Where, and how, a round is saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveTarget {
+    pub path: PathBuf,
+    pub obfuscate: bool,
+}
+
+impl SaveTarget {
+    // `elapsed` is how long the round has been played so far.
+    pub fn save(&self, game: &Game, elapsed: Duration) -> io::Result<()> {
+        SavedGame::from_game(game, self.obfuscate, elapsed).write(&self.path)
+    }
+}
+
+// Keeps a copy of the round as it is whenever the player is asked for a
+// guess, so that it can be saved from elsewhere, e.g. when the player hits
+// Ctrl-C. Everything else is passed on to the wrapped reporter.
+pub struct Autosave<'a> {
+    inner: Box<dyn Reporter + 'a>,
+    snapshot: Arc<Mutex<Option<Game>>>,
+}
+
+impl<'a> Autosave<'a> {
+    pub fn new(inner: Box<dyn Reporter + 'a>, snapshot: Arc<Mutex<Option<Game>>>) -> Autosave<'a> {
+        Autosave { inner, snapshot }
+    }
+}
+
+impl Reporter for Autosave<'_> {
+    fn start(&mut self, game: &Game) -> io::Result<()> {
+        self.inner.start(game)
+    }
+
+    fn prompt(&mut self, game: &Game) -> io::Result<()> {
+        *self.snapshot.lock().unwrap() = Some(game.clone());
+        self.inner.prompt(game)
+    }
+
+    fn time_left(&mut self, left: Duration) -> io::Result<()> {
+        self.inner.time_left(left)
+    }
+
+    fn echo(&mut self, guess: u32) -> io::Result<()> {
+        self.inner.echo(guess)
+    }
+
+    fn invalid(&mut self, game: &Game, input: &str, error: &InputError) -> io::Result<()> {
+        self.inner.invalid(game, input, error)
+    }
+
+    fn guessed(&mut self, game: &Game, guess: u32, outcome: Outcome) -> io::Result<()> {
+        self.inner.guessed(game, guess, outcome)
+    }
+
+    fn hint(&mut self, game: &Game, clue: &Clue) -> io::Result<()> {
+        self.inner.hint(game, clue)
+    }
+
+    fn no_hint(&mut self, game: &Game, reason: NoHint) -> io::Result<()> {
+        self.inner.no_hint(game, reason)
+    }
+
+    fn history(&mut self, game: &Game) -> io::Result<()> {
+        self.inner.history(game)
+    }
+
+    fn help(&mut self) -> io::Result<()> {
+        self.inner.help()
+    }
+
+    fn saved(&mut self, path: &Path) -> io::Result<()> {
+        self.inner.saved(path)
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()> {
+        // A finished round is not worth saving.
+        *self.snapshot.lock().unwrap() = None;
+        self.inner.finished(game)
+    }
+
+    fn splits(&mut self, splits: &[Duration], total: Duration) -> io::Result<()> {
+        self.inner.splits(splits, total)
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::calendar::civil_from_days;
+use crate::game::{Game, Host};
+
+// A won round, as kept in the high-score table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub name: String,
+    // Seconds since the Unix epoch.
+    pub date: u64,
+    pub min: u32,
+    pub max: u32,
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+    // Whether the host was evil, and whether any hints were asked for. Such
+    // rounds are ranked apart from honest ones. Missing from older files.
+    #[serde(default)]
+    pub evil: bool,
+    #[serde(default)]
+    pub hints: bool,
+}
+
+impl Score {
+    // The score for `game`, won by `name` in `elapsed`.
+    pub fn new(name: &str, game: &Game, elapsed: Duration) -> Score {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        Score {
+            name: name.to_string(),
+            date,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            attempts: game.attempts(),
+            elapsed_ms: elapsed.as_millis() as u64,
+            evil: game.host() == Host::Evil,
+            hints: !game.clues().is_empty(),
+        }
+    }
+
+    pub fn table(&self) -> Table {
+        Table {
+            min: self.min,
+            max: self.max,
+            evil: self.evil,
+            hints: self.hints,
+        }
+    }
+
+    // The date as `YYYY-MM-DD` in UTC.
+    pub fn day(&self) -> String {
+        let (year, month, day) = civil_from_days((self.date / 86_400) as i64);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+// Which rounds are ranked against each other: those of the same range,
+// with the same kind of host, and either all with hints or none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Table {
+    pub min: u32,
+    pub max: u32,
+    pub evil: bool,
+    pub hints: bool,
+}
+
+// The scores read from a file, and how many lines of it had to be skipped
+// because they were not valid scores.
+#[derive(Debug, Clone, Default)]
+pub struct Loaded {
+    pub scores: Vec<Score>,
+    pub skipped: usize,
+}
+
+// The high-score table is a file with one JSON score per line. New scores
+// are appended with a single write to a file opened in append mode, so
+// several games finishing at once do not overwrite each other. A line that
+// can not be read, e.g. one cut short by a crash, is skipped on its own.
+pub struct ScoreFile {
+    path: PathBuf,
+}
+
+impl ScoreFile {
+    pub fn new(path: impl Into<PathBuf>) -> ScoreFile {
+        ScoreFile { path: path.into() }
+    }
+
+    // `$XDG_DATA_HOME/guessing_game/scores.jsonl`, falling back to
+    // `~/.local/share` when `XDG_DATA_HOME` is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        let data_home = match env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+        };
+        Some(data_home.join("guessing_game").join("scores.jsonl"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, score: &Score) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut line = serde_json::to_string(score)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    // A missing file is just an empty table.
+    pub fn load(&self) -> io::Result<Loaded> {
+        let contents = match fs::read(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Loaded::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut loaded = Loaded::default();
+        for line in contents.split(|&byte| byte == b'\n') {
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+
+            match serde_json::from_slice(line) {
+                Ok(score) => loaded.scores.push(score),
+                Err(_) => loaded.skipped += 1,
+            }
+        }
+
+        Ok(loaded)
+    }
+}
+
+// Groups the scores by table, best first: fewest attempts, then fastest.
+// Only the `top` best of every table are kept.
+pub fn best_by_table(scores: &[Score], top: usize) -> BTreeMap<Table, Vec<Score>> {
+    let mut tables: BTreeMap<Table, Vec<Score>> = BTreeMap::new();
+    for score in scores {
+        tables.entry(score.table()).or_default().push(score.clone());
+    }
+
+    for best in tables.values_mut() {
+        best.sort_by_key(|score| (score.attempts, score.elapsed_ms, score.date));
+        best.truncate(top);
+    }
+
+    tables
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::game::{Game, State};
+use crate::strategy::{StrategyKind, MAX_GUESSES};
+
+// How one strategy did over a batch of games.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub strategy: StrategyKind,
+    pub games: u32,
+    pub mean: f64,
+    pub median: f64,
+    pub max: u32,
+    // The number of games won within the attempt limit, or within
+    // `MAX_GUESSES` if there is no limit.
+    pub wins: u32,
+    // How many games took a given number of attempts.
+    pub histogram: BTreeMap<u32, u32>,
+}
+
+impl Summary {
+    pub fn win_rate(&self) -> f64 {
+        f64::from(self.wins) / f64::from(self.games)
+    }
+}
+
+// Plays `games` rounds with `strategy`, where round `i` uses the secret number
+// drawn from `seed + i`. Every strategy therefore faces the same secrets.
+// A round stops at `max_attempts`, or at `MAX_GUESSES` without a limit, and
+// is then lost; its attempts are counted up to where it stopped.
+pub fn simulate(
+    strategy: StrategyKind,
+    range: &RangeInclusive<u32>,
+    games: u32,
+    seed: u64,
+    max_attempts: Option<u32>,
+) -> Summary {
+    let mut attempts = Vec::with_capacity(games as usize);
+    let mut wins = 0;
+
+    for i in 0..games {
+        let game_seed = seed.wrapping_add(u64::from(i));
+        let game = Game::from_rng(range.clone(), &mut StdRng::seed_from_u64(game_seed));
+        let mut game = game.with_max_attempts(max_attempts.unwrap_or(MAX_GUESSES).min(MAX_GUESSES));
+        let mut player = strategy.build(range, Some(game_seed));
+
+        while game.state() == State::Playing {
+            let guess = player.next_guess();
+            let outcome = game.guess(guess);
+            player.feedback(guess, outcome.ordering());
+        }
+
+        if game.state() == State::Won {
+            wins += 1;
+        }
+        attempts.push(game.attempts());
+    }
+
+    attempts.sort_unstable();
+
+    let mut histogram = BTreeMap::new();
+    for &n in &attempts {
+        *histogram.entry(n).or_insert(0) += 1;
+    }
+
+    let total: u64 = attempts.iter().map(|&n| u64::from(n)).sum();
+    let middle = attempts.len() / 2;
+    let median = if attempts.len() % 2 == 0 {
+        (f64::from(attempts[middle - 1]) + f64::from(attempts[middle])) / 2.0
+    } else {
+        f64::from(attempts[middle])
+    };
+    Summary {
+        strategy,
+        games,
+        mean: total as f64 / f64::from(games),
+        median,
+        max: attempts[attempts.len() - 1],
+        wins,
+        histogram,
+    }
+}
+
+pub fn write_text<W: Write>(summaries: &[Summary], mut output: W) -> io::Result<()> {
+    for summary in summaries {
+        writeln!(
+            output,
+            "{}: {} games, mean {:.2}, median {}, max {}, win rate {:.1}%",
+            summary.strategy,
+            summary.games,
+            summary.mean,
+            summary.median,
+            summary.max,
+            summary.win_rate() * 100.0
+        )?;
+
+        // Scale the bars so that the most common attempt count gets 40 marks.
+        let most = summary.histogram.values().copied().max().unwrap_or(1);
+        for (attempts, count) in &summary.histogram {
+            let width = (u64::from(*count) * 40).div_ceil(u64::from(most)) as usize;
+            writeln!(output, "{attempts:>6} | {:<40} {count}", "#".repeat(width))?;
+        }
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+// One row per strategy. The histogram is packed into a single column as
+// `attempts:count` pairs separated by `;`.
+pub fn write_csv<W: Write>(summaries: &[Summary], mut output: W) -> io::Result<()> {
+    writeln!(output, "strategy,games,mean,median,max,wins,win_rate,histogram")?;
+
+    for summary in summaries {
+        let histogram: Vec<String> = summary
+            .histogram
+            .iter()
+            .map(|(attempts, count)| format!("{attempts}:{count}"))
+            .collect();
+
+        writeln!(
+            output,
+            "{},{},{:.4},{},{},{},{:.4},{}",
+            summary.strategy,
+            summary.games,
+            summary.mean,
+            summary.median,
+            summary.max,
+            summary.wins,
+            summary.win_rate(),
+            histogram.join(";")
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::interval::Interval;
+
+// The most guesses a strategy gets to find the number when there is no
+// attempt limit. A linear scan of the insane range would take billions, each
+// kept in the round's history.
+pub const MAX_GUESSES: u32 = 10_000;
+
+// A player that picks guesses on its own. After every guess it is told how
+// the guess compared to the secret number, i.e. `guess.cmp(&secret_number)`.
+pub trait Strategy {
+    fn next_guess(&mut self) -> u32;
+    fn feedback(&mut self, guess: u32, answer: Ordering);
+}
+
+// Always guesses the middle of the remaining candidates, halving them with
+// every answer.
+pub struct BinarySearch {
+    candidates: Interval,
+}
+
+impl BinarySearch {
+    pub fn new(range: &RangeInclusive<u32>) -> BinarySearch {
+        BinarySearch {
+            candidates: Interval::new(range),
+        }
+    }
+}
+
+impl Strategy for BinarySearch {
+    fn next_guess(&mut self) -> u32 {
+        self.candidates.midpoint()
+    }
+
+    fn feedback(&mut self, guess: u32, answer: Ordering) {
+        self.candidates.narrow(guess, answer);
+    }
+}
+
+// Guesses any of the remaining candidates at random.
+pub struct Random<R: RngCore> {
+    candidates: Interval,
+    rng: R,
+}
+
+impl<R: RngCore> Random<R> {
+    pub fn new(range: &RangeInclusive<u32>, rng: R) -> Random<R> {
+        Random {
+            candidates: Interval::new(range),
+            rng,
+        }
+    }
+}
+
+impl<R: RngCore> Strategy for Random<R> {
+    fn next_guess(&mut self) -> u32 {
+        self.rng
+            .gen_range(self.candidates.low()..=self.candidates.high())
+    }
+
+    fn feedback(&mut self, guess: u32, answer: Ordering) {
+        self.candidates.narrow(guess, answer);
+    }
+}
+
+// Counts upwards from the smallest number, ignoring everything but a win.
+pub struct LinearScan {
+    next: u32,
+}
+
+impl LinearScan {
+    pub fn new(range: &RangeInclusive<u32>) -> LinearScan {
+        LinearScan {
+            next: *range.start(),
+        }
+    }
+}
+
+impl Strategy for LinearScan {
+    fn next_guess(&mut self) -> u32 {
+        self.next
+    }
+
+    fn feedback(&mut self, guess: u32, _answer: Ordering) {
+        self.next = guess.saturating_add(1);
+    }
+}
+
+// Expects every answer to be as unhelpful as possible, so it never risks a
+// guess that could rule out more than one candidate: it takes turns guessing
+// the lowest and the highest number still possible. This is the worst way
+// to play that still never makes a guess the answers have ruled out.
+pub struct Pessimistic {
+    candidates: Interval,
+    from_below: bool,
+}
+
+impl Pessimistic {
+    pub fn new(range: &RangeInclusive<u32>) -> Pessimistic {
+        Pessimistic {
+            candidates: Interval::new(range),
+            from_below: true,
+        }
+    }
+}
+
+impl Strategy for Pessimistic {
+    fn next_guess(&mut self) -> u32 {
+        if self.from_below {
+            self.candidates.low()
+        } else {
+            self.candidates.high()
+        }
+    }
+
+    fn feedback(&mut self, guess: u32, answer: Ordering) {
+        self.candidates.narrow(guess, answer);
+        self.from_below = !self.from_below;
+    }
+}
+
+// The built-in strategies, as named on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyKind {
+    BinarySearch,
+    Random,
+    LinearScan,
+    Pessimistic,
+}
+
+impl StrategyKind {
+    // `seed` is the seed the game was started with, which only matters to the
+    // random strategy. It derives a seed of its own from it, or it would draw
+    // the very same number as the secret on its first guess.
+    pub fn build(self, range: &RangeInclusive<u32>, seed: Option<u64>) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::BinarySearch => Box::new(BinarySearch::new(range)),
+            StrategyKind::Random => {
+                let rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(1)),
+                    None => StdRng::from_entropy(),
+                };
+                Box::new(Random::new(range, rng))
+            }
+            StrategyKind::LinearScan => Box::new(LinearScan::new(range)),
+            StrategyKind::Pessimistic => Box::new(Pessimistic::new(range)),
+        }
+    }
+}
+
+impl FromStr for StrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<StrategyKind, String> {
+        match s {
+            "binary" => Ok(StrategyKind::BinarySearch),
+            "random" => Ok(StrategyKind::Random),
+            "linear" => Ok(StrategyKind::LinearScan),
+            "pessimistic" => Ok(StrategyKind::Pessimistic),
+            _ => Err(format!(
+                "unknown strategy '{s}', expected binary, random, linear or pessimistic"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for StrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StrategyKind::BinarySearch => "binary",
+            StrategyKind::Random => "random",
+            StrategyKind::LinearScan => "linear",
+            StrategyKind::Pessimistic => "pessimistic",
+        };
+        write!(f, "{name}")
+    }
+}
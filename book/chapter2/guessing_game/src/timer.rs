@@ -0,0 +1,137 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+// Where the time comes from. The game only ever asks how much time has
+// passed since some fixed point, so tests can use a clock they move by hand.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+// The real time, measured from when the clock was created.
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+// A clock that only moves when told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock::default()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+// Times a round from the first prompt, with an optional time limit, and
+// remembers when every guess was made.
+pub struct Timer<'a> {
+    clock: &'a dyn Clock,
+    started: Option<Duration>,
+    stopped: Option<Duration>,
+    limit: Option<Duration>,
+    splits: Vec<Duration>,
+    // Time played before the round was saved, when it is resumed.
+    earlier: Duration,
+}
+
+impl<'a> Timer<'a> {
+    pub fn new(clock: &'a dyn Clock) -> Timer<'a> {
+        Timer {
+            clock,
+            started: None,
+            stopped: None,
+            limit: None,
+            splits: Vec::new(),
+            earlier: Duration::ZERO,
+        }
+    }
+
+    pub fn with_limit(mut self, limit: Duration) -> Timer<'a> {
+        self.limit = Some(limit);
+        self
+    }
+
+    // Counts `earlier` as already played, for a round resumed from a save.
+    pub fn with_elapsed(mut self, earlier: Duration) -> Timer<'a> {
+        self.earlier = earlier;
+        self
+    }
+
+    // Starts the timer, unless it is already running.
+    pub fn start(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(self.clock.now());
+        }
+    }
+
+    // Stops the timer for good, so that `elapsed` no longer changes.
+    pub fn stop(&mut self) {
+        if self.stopped.is_none() {
+            self.stopped = Some(self.clock.now());
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        let now = self.stopped.unwrap_or_else(|| self.clock.now());
+        let since_start = match self.started {
+            Some(started) => now.saturating_sub(started),
+            None => Duration::ZERO,
+        };
+        self.earlier + since_start
+    }
+
+    pub fn limit(&self) -> Option<Duration> {
+        self.limit
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        self.limit.map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    // Notes that a guess was made just now.
+    pub fn split(&mut self) {
+        let elapsed = self.elapsed();
+        self.splits.push(elapsed);
+    }
+
+    // When every guess was made, counted from the start of the round.
+    pub fn splits(&self) -> &[Duration] {
+        &self.splits
+    }
+}
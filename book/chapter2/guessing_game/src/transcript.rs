@@ -0,0 +1,303 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+// The first line of a transcript: how the round was started, so that it can
+// be played again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    // The command line arguments, without the program name.
+    pub args: Vec<String>,
+    pub seed: u64,
+    pub format: String,
+}
+
+// Every other line is something the player typed or the game printed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    // Milliseconds since the transcript was started.
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub text: Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Text {
+    Input(String),
+    Output(String),
+}
+
+// A transcript being written, one JSON line per entry as it happens, so that
+// nothing is lost if the program is killed.
+pub struct Transcript {
+    file: Mutex<File>,
+    started: Instant,
+}
+
+impl Transcript {
+    pub fn create(path: &Path, header: &Header) -> io::Result<Arc<Transcript>> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(header)?)?;
+
+        Ok(Arc::new(Transcript {
+            file: Mutex::new(file),
+            started: Instant::now(),
+        }))
+    }
+
+    fn record(&self, text: Text) -> io::Result<()> {
+        let entry = Entry {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            text,
+        };
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+    }
+}
+
+// Passes writes on to `inner` and records them as output, a line at a time
+// so that the transcript is not split up into every little `write!`.
+pub struct RecordedOutput<W: Write> {
+    inner: W,
+    transcript: Arc<Transcript>,
+    line: Vec<u8>,
+}
+
+impl<W: Write> RecordedOutput<W> {
+    pub fn new(inner: W, transcript: Arc<Transcript>) -> RecordedOutput<W> {
+        RecordedOutput {
+            inner,
+            transcript,
+            line: Vec::new(),
+        }
+    }
+
+    fn take_line(&mut self) -> io::Result<()> {
+        let line = String::from_utf8_lossy(&self.line).into_owned();
+        self.line.clear();
+        self.transcript.record(Text::Output(line))
+    }
+}
+
+impl<W: Write> Write for RecordedOutput<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        for &byte in &buf[..written] {
+            self.line.push(byte);
+            if byte == b'\n' {
+                self.take_line()?;
+            }
+        }
+        Ok(written)
+    }
+
+    // Anything flushed has been seen by the player, so it is recorded even
+    // without a newline.
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            self.take_line()?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for RecordedOutput<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+// Passes reads on to `inner` and records every line read as input.
+pub struct RecordedInput<R: BufRead> {
+    inner: R,
+    transcript: Arc<Transcript>,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> RecordedInput<R> {
+    pub fn new(inner: R, transcript: Arc<Transcript>) -> RecordedInput<R> {
+        RecordedInput {
+            inner,
+            transcript,
+            line: Vec::new(),
+        }
+    }
+
+    fn take_line(&mut self) -> io::Result<()> {
+        let line = String::from_utf8_lossy(&self.line).into_owned();
+        self.line.clear();
+        self.transcript.record(Text::Input(line))
+    }
+}
+
+impl<R: BufRead> Read for RecordedInput<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for RecordedInput<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.inner.fill_buf()?.is_empty() && !self.line.is_empty() {
+            // The input ended without a final newline.
+            self.take_line()?;
+        }
+        self.inner.fill_buf()
+    }
+
+    // Whatever is consumed is still in the buffer of `inner` at this point.
+    fn consume(&mut self, amt: usize) {
+        let mut finished = Vec::new();
+        if let Ok(available) = self.inner.fill_buf() {
+            for &byte in &available[..amt.min(available.len())] {
+                self.line.push(byte);
+                if byte == b'\n' {
+                    finished.push(std::mem::take(&mut self.line));
+                }
+            }
+        }
+        self.inner.consume(amt);
+
+        // `consume` can not fail, so a line that can not be recorded is
+        // only reported.
+        for line in finished {
+            let line = String::from_utf8_lossy(&line).into_owned();
+            if let Err(e) = self.transcript.record(Text::Input(line)) {
+                eprintln!("Failed to record the input: {e}");
+            }
+        }
+    }
+}
+
+// A transcript read back from a file.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub header: Header,
+    pub entries: Vec<Entry>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> io::Result<Recording> {
+        let invalid = |e: serde_json::Error| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut lines = BufReader::new(File::open(path)?).lines();
+
+        let header = match lines.next() {
+            Some(line) => serde_json::from_str(&line?).map_err(invalid)?,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData, "the transcript is empty")),
+        };
+
+        let mut entries = Vec::new();
+        for line in lines {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line).map_err(invalid)?);
+            }
+        }
+
+        Ok(Recording { header, entries })
+    }
+
+    // Everything the player typed, in order.
+    pub fn input(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.text {
+                Text::Input(text) => Some(text.as_str()),
+                Text::Output(_) => None,
+            })
+            .collect()
+    }
+
+    // Everything the game printed, in order.
+    pub fn output(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.text {
+                Text::Output(text) => Some(text.as_str()),
+                Text::Input(_) => None,
+            })
+            .collect()
+    }
+
+    // Writes the session to `output` with the original pauses between the
+    // entries divided by `speed`, or without any pauses if it is 0. Input is
+    // shown as it was typed.
+    pub fn replay<W: Write>(&self, speed: u32, mut output: W) -> io::Result<()> {
+        let mut previous = 0;
+        for entry in &self.entries {
+            let pause = Duration::from_millis(entry.at_ms.saturating_sub(previous));
+            if speed > 0 {
+                thread::sleep(pause / speed);
+            }
+            previous = entry.at_ms;
+
+            match &entry.text {
+                Text::Input(text) | Text::Output(text) => output.write_all(text.as_bytes())?,
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    // Runs `program` again with the recorded arguments and seed, feeds it the
+    // recorded input and returns whatever it printed.
+    pub fn rerun(&self, program: &Path) -> io::Result<String> {
+        let mut child = Command::new(program)
+            .args(&self.header.args)
+            .arg("--seed")
+            .arg(self.header.seed.to_string())
+            .arg("--format")
+            .arg(&self.header.format)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // The game answers as it reads, so the input is written from another
+        // thread to keep both pipes flowing.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = self.input();
+        let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let output = child.wait_with_output()?;
+        // The game may quit before reading all of it, e.g. after winning.
+        let _ = writer.join();
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+// Where two outputs first differ, as the line number counting from 1 and
+// the two lines.
+pub fn first_difference(expected: &str, actual: &str) -> Option<(usize, String, String)> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+
+    for number in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (left, right) if left != right => {
+                return Some((
+                    number,
+                    left.unwrap_or("<end of output>").to_string(),
+                    right.unwrap_or("<end of output>").to_string(),
+                ))
+            }
+            _ => {}
+        }
+    }
+    unreachable!()
+}
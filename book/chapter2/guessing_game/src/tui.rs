@@ -0,0 +1,249 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, Outcome, State};
+use crate::hint::{Clue, NoHint};
+use crate::input::{InputError, COMMANDS};
+use crate::report::Reporter;
+
+const BAR_WIDTH: u64 = 50;
+const HISTORY_LINES: usize = 10;
+
+// Clears the terminal and moves the cursor to the top left corner.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+// A full-screen view of the round, redrawn after every event. It shows the
+// numbers that are still possible as a bar, the latest guesses, the attempts
+// left and a clock that keeps ticking while the player is typing.
+pub struct Tui<W: Write + Send + 'static> {
+    output: Arc<Mutex<W>>,
+    limit: Option<Duration>,
+    started: Instant,
+    message: String,
+    ticking: Arc<AtomicBool>,
+    ticker: Option<JoinHandle<()>>,
+}
+
+impl<W: Write + Send + 'static> Tui<W> {
+    // `limit` is the time limit of the round, if it has one.
+    pub fn new(output: W, limit: Option<Duration>) -> Tui<W> {
+        Tui {
+            output: Arc::new(Mutex::new(output)),
+            limit,
+            started: Instant::now(),
+            message: String::new(),
+            ticking: Arc::new(AtomicBool::new(false)),
+            ticker: None,
+        }
+    }
+
+    fn draw(&mut self, game: &Game, prompt: bool) -> io::Result<()> {
+        let mut screen = String::from(CLEAR);
+        let range = game.range();
+
+        screen.push_str("Guessing the number!\n");
+        screen.push_str(&clock_line(self.started.elapsed(), self.limit));
+        screen.push('\n');
+
+        match game.attempts_remaining() {
+            Some(left) => screen.push_str(&format!("Attempts: {} ({left} left)\n", game.attempts())),
+            None => screen.push_str(&format!("Attempts: {}\n", game.attempts())),
+        }
+        screen.push('\n');
+
+        let candidates = game.candidates();
+        screen.push_str(&format!(
+            "{} [{}] {}\n",
+            range.start(),
+            bar(game),
+            range.end()
+        ));
+        if candidates.len() == 1 {
+            screen.push_str(&format!("It can only be {}.\n", candidates.low()));
+        } else {
+            screen.push_str(&format!(
+                "It is between {} and {} ({} numbers).\n",
+                candidates.low(),
+                candidates.high(),
+                candidates.len()
+            ));
+        }
+        screen.push('\n');
+
+        let history = game.history();
+        let skipped = history.len().saturating_sub(HISTORY_LINES);
+        screen.push_str("History:\n");
+        for (i, (guess, outcome)) in history.iter().enumerate().skip(skipped) {
+            let answer = match outcome {
+                Outcome::Less => "too small",
+                Outcome::Greater => "too big",
+                Outcome::Won => "correct",
+            };
+            screen.push_str(&format!("{:>4}. {guess:>10}  {answer}\n", i + 1));
+        }
+        screen.push('\n');
+
+        screen.push_str(&self.message);
+        screen.push('\n');
+        if prompt {
+            screen.push_str("> ");
+        }
+
+        let mut output = self.output.lock().unwrap();
+        output.write_all(screen.as_bytes())?;
+        output.flush()
+    }
+
+    // Redraws the clock line whenever it changes, leaving the cursor where the
+    // player is typing.
+    fn start_ticking(&mut self) {
+        self.ticking.store(true, Ordering::SeqCst);
+
+        let output = Arc::clone(&self.output);
+        let ticking = Arc::clone(&self.ticking);
+        let started = self.started;
+        let limit = self.limit;
+
+        self.ticker = Some(thread::spawn(move || {
+            let mut shown = clock_line(started.elapsed(), limit);
+            while ticking.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(200));
+
+                let line = clock_line(started.elapsed(), limit);
+                if line == shown {
+                    continue;
+                }
+                shown = line.clone();
+
+                let mut output = output.lock().unwrap();
+                // Save the cursor, rewrite the second line and restore it.
+                let _ = write!(output, "\x1b7\x1b[2;1H\x1b[2K{line}\x1b8");
+                let _ = output.flush();
+            }
+        }));
+    }
+
+    fn stop_ticking(&mut self) {
+        self.ticking.store(false, Ordering::SeqCst);
+        if let Some(ticker) = self.ticker.take() {
+            let _ = ticker.join();
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> Reporter for Tui<W> {
+    fn start(&mut self, _game: &Game) -> io::Result<()> {
+        self.started = Instant::now();
+        self.start_ticking();
+        Ok(())
+    }
+
+    fn prompt(&mut self, game: &Game) -> io::Result<()> {
+        self.draw(game, true)
+    }
+
+    fn echo(&mut self, guess: u32) -> io::Result<()> {
+        let mut output = self.output.lock().unwrap();
+        writeln!(output, "{guess}")
+    }
+
+    fn invalid(&mut self, _game: &Game, _input: &str, error: &InputError) -> io::Result<()> {
+        self.message = error.to_string();
+        Ok(())
+    }
+
+    fn guessed(&mut self, _game: &Game, guess: u32, outcome: Outcome) -> io::Result<()> {
+        self.message = match outcome {
+            Outcome::Less => format!("{guess} is too small."),
+            Outcome::Greater => format!("{guess} is too big."),
+            Outcome::Won => format!("{guess} is correct!"),
+        };
+        Ok(())
+    }
+
+    fn hint(&mut self, _game: &Game, clue: &Clue) -> io::Result<()> {
+        self.message = format!("{clue} (-{} points)", clue.hint().cost());
+        Ok(())
+    }
+
+    fn no_hint(&mut self, _game: &Game, reason: NoHint) -> io::Result<()> {
+        self.message = reason.to_string();
+        Ok(())
+    }
+
+    // The history is always on screen, so there is little to add.
+    fn history(&mut self, game: &Game) -> io::Result<()> {
+        self.message = format!("{} guesses so far, the last {HISTORY_LINES} are shown above.", game.history().len());
+        Ok(())
+    }
+
+    fn help(&mut self) -> io::Result<()> {
+        let commands: Vec<&str> = COMMANDS.iter().map(|(command, _)| *command).collect();
+        self.message = format!("Type a number, or one of: {}", commands.join(", "));
+        Ok(())
+    }
+
+    fn saved(&mut self, path: &Path) -> io::Result<()> {
+        self.stop_ticking();
+        let mut output = self.output.lock().unwrap();
+        writeln!(output, "\nGame saved, continue with --resume {}", path.display())
+    }
+
+    fn finished(&mut self, game: &Game) -> io::Result<()> {
+        self.stop_ticking();
+
+        let secret_number = game.secret_number();
+        self.message = match game.state() {
+            State::Won => format!("You won in {} attempts!", game.attempts()),
+            State::Lost => format!("You lost! The secret number was {secret_number}."),
+            State::GaveUp => format!("Giving up? The secret number was {secret_number}."),
+            State::OutOfTime => format!("Time is up! The secret number was {secret_number}."),
+            State::Playing => String::new(),
+        };
+        if game.state() != State::Playing {
+            self.message.push_str(&format!("\nScore: {}", game.points()));
+        }
+        self.draw(game, false)
+    }
+}
+
+impl<W: Write + Send + 'static> Drop for Tui<W> {
+    fn drop(&mut self) {
+        self.stop_ticking();
+    }
+}
+
+fn clock_line(elapsed: Duration, limit: Option<Duration>) -> String {
+    match limit {
+        Some(limit) => format!(
+            "Time: {}s ({}s left)",
+            elapsed.as_secs(),
+            limit.saturating_sub(elapsed).as_secs()
+        ),
+        None => format!("Time: {}s", elapsed.as_secs()),
+    }
+}
+
+// The range drawn as a bar, with the numbers that are still possible marked
+// by `#`.
+fn bar(game: &Game) -> String {
+    let start = u64::from(*game.range().start());
+    let size = u64::from(*game.range().end()) - start + 1;
+    let candidates = game.candidates();
+
+    (0..BAR_WIDTH)
+        .map(|cell| {
+            // The numbers this cell stands for.
+            let first = start + cell * size / BAR_WIDTH;
+            let last = (start + (cell + 1) * size / BAR_WIDTH).max(first + 1) - 1;
+            let possible = !candidates.is_empty()
+                && first <= u64::from(candidates.high())
+                && u64::from(candidates.low()) <= last;
+            if possible { '#' } else { '-' }
+        })
+        .collect()
+}
@@ -0,0 +1,83 @@
+use crate::game::State;
+
+// The bookkeeping every kind of round shares: each guess made while the
+// round was being played with the answer it got, the limit on attempts and
+// the state the round is in. The rounds only work out the answers, and like
+// this none of them knows anything about stdin or stdout, so any front-end
+// can drive them.
+#[derive(Debug, Clone)]
+pub struct Turns<G, A> {
+    history: Vec<(G, A)>,
+    limit: Option<u32>,
+    state: State,
+}
+
+impl<G, A> Turns<G, A> {
+    pub fn new() -> Turns<G, A> {
+        Turns {
+            history: Vec::new(),
+            limit: None,
+            state: State::Playing,
+        }
+    }
+
+    // Limits the round to `limit` guesses, after which it is lost.
+    pub fn with_limit(mut self, limit: u32) -> Turns<G, A> {
+        self.limit = Some(limit);
+        self
+    }
+
+    // Counts `guess` and its `answer`, unless the round is over. `won` says
+    // whether the guess found the secret; if not, the last attempt loses.
+    pub fn record(&mut self, guess: G, answer: A, won: bool) {
+        if self.state != State::Playing {
+            return;
+        }
+        self.history.push((guess, answer));
+
+        if won {
+            self.state = State::Won;
+        } else if self.remaining() == Some(0) {
+            self.state = State::Lost;
+        }
+    }
+
+    // Ends the round as `state`, e.g. when the player gives up, unless it is
+    // already over.
+    pub fn end(&mut self, state: State) {
+        if self.state == State::Playing {
+            self.state = state;
+        }
+    }
+
+    // Every guess made while the round was being played, with its answer.
+    pub fn history(&self) -> &[(G, A)] {
+        &self.history
+    }
+
+    pub fn count(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+
+    pub fn remaining(&self) -> Option<u32> {
+        self.limit.map(|limit| limit.saturating_sub(self.count()))
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state == State::Playing
+    }
+}
+
+impl<G, A> Default for Turns<G, A> {
+    fn default() -> Turns<G, A> {
+        Turns::new()
+    }
+}
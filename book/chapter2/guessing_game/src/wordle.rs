@@ -0,0 +1,222 @@
+use std::fmt;
+use rand::{Rng, RngCore};
+
+use crate::domain::Words;
+use crate::game::State;
+use crate::turns::Turns;
+
+// How many guesses a round of Wordle has, unless told otherwise.
+pub const DEFAULT_ATTEMPTS: u32 = 6;
+
+// What a guess tells about a single letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    // The letter is in the secret word at this very position.
+    Correct,
+    // The letter is in the secret word, but somewhere else.
+    Present,
+    // The letter is not in the secret word, or not that many times.
+    Absent,
+}
+
+// Marks every letter of `guess`, which has as many letters as `secret`.
+// A letter that is in the guess more often than in the secret is only marked
+// present as many times as the secret has it, correct positions first.
+pub fn score(guess: &str, secret: &str) -> Vec<Mark> {
+    let guess: Vec<char> = guess.chars().collect();
+    let secret: Vec<char> = secret.chars().collect();
+
+    let mut marks = vec![Mark::Absent; guess.len()];
+    // The letters of the secret not matched by a correct guess yet.
+    let mut unmatched = Vec::new();
+    for (i, &letter) in secret.iter().enumerate() {
+        if guess[i] == letter {
+            marks[i] = Mark::Correct;
+        } else {
+            unmatched.push(letter);
+        }
+    }
+
+    for (i, letter) in guess.iter().enumerate() {
+        if marks[i] == Mark::Correct {
+            continue;
+        }
+        if let Some(position) = unmatched.iter().position(|other| other == letter) {
+            unmatched.swap_remove(position);
+            marks[i] = Mark::Present;
+        }
+    }
+
+    marks
+}
+
+// Why a guess is no word to play, which costs no attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejected {
+    WrongLength { expected: usize },
+    NotAWord,
+    // Hard mode: a letter found in the right place has to stay there.
+    MustKeep { position: usize, letter: char },
+    // Hard mode: every letter found so far has to be used.
+    MustUse(char),
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejected::WrongLength { expected } => write!(f, "The word has {expected} letters."),
+            Rejected::NotAWord => write!(f, "That is not in the word list."),
+            Rejected::MustKeep { position, letter } => {
+                write!(f, "Letter {position} has to be {}.", letter.to_ascii_uppercase())
+            }
+            Rejected::MustUse(letter) => write!(f, "The guess has to contain {}.", letter.to_ascii_uppercase()),
+        }
+    }
+}
+
+// A round of Wordle, where every guess has to be a word from the list.
+#[derive(Debug, Clone)]
+pub struct Wordle {
+    words: Words,
+    secret: String,
+    hard: bool,
+    turns: Turns<String, Vec<Mark>>,
+}
+
+impl Wordle {
+    // Guesses have to be in `words`. Panics if `secret` is not.
+    pub fn new(words: Words, secret: &str) -> Wordle {
+        assert!(words.contains(secret), "the secret word {secret} is not in the word list");
+
+        Wordle {
+            words,
+            secret: secret.to_string(),
+            hard: false,
+            turns: Turns::new(),
+        }
+    }
+
+    // Draws the secret word from `rng`.
+    pub fn from_rng<R: RngCore + ?Sized>(words: Words, rng: &mut R) -> Wordle {
+        let secret = words.words()[rng.gen_range(0..words.words().len())].clone();
+        Wordle::new(words, &secret)
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Wordle {
+        self.turns = self.turns.with_limit(max_attempts);
+        self
+    }
+
+    // In hard mode every guess has to fit what the earlier ones revealed.
+    pub fn with_hard_mode(mut self) -> Wordle {
+        self.hard = true;
+        self
+    }
+
+    pub fn guess(&mut self, guess: &str) -> Result<Vec<Mark>, Rejected> {
+        let guess = guess.to_lowercase();
+
+        let expected = self.secret.chars().count();
+        if guess.chars().count() != expected {
+            return Err(Rejected::WrongLength { expected });
+        }
+        if !self.words.contains(&guess) {
+            return Err(Rejected::NotAWord);
+        }
+        if self.hard {
+            self.check_hard_mode(&guess)?;
+        }
+
+        let marks = score(&guess, &self.secret);
+        let won = marks.iter().all(|&mark| mark == Mark::Correct);
+        self.turns.record(guess, marks.clone(), won);
+
+        Ok(marks)
+    }
+
+    fn check_hard_mode(&self, guess: &str) -> Result<(), Rejected> {
+        let letters: Vec<char> = guess.chars().collect();
+
+        for (earlier, marks) in self.turns.history() {
+            for (position, (letter, mark)) in earlier.chars().zip(marks).enumerate() {
+                if *mark == Mark::Correct && letters[position] != letter {
+                    return Err(Rejected::MustKeep {
+                        position: position + 1,
+                        letter,
+                    });
+                }
+            }
+
+            // A letter found twice has to be used twice.
+            let mut left = letters.clone();
+            for (letter, mark) in earlier.chars().zip(marks) {
+                if *mark == Mark::Absent {
+                    continue;
+                }
+                match left.iter().position(|&other| other == letter) {
+                    Some(position) => {
+                        left.swap_remove(position);
+                    }
+                    None => return Err(Rejected::MustUse(letter)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn give_up(&mut self) {
+        self.turns.end(State::GaveUp);
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    // Every guess with its marks, the attempts left and how the round stands.
+    pub fn turns(&self) -> &Turns<String, Vec<Mark>> {
+        &self.turns
+    }
+}
+
+// How the marks are shown: colored letters for a terminal, or squares after
+// the word for anything else, like the results people share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Color,
+    Symbols,
+}
+
+pub fn render(guess: &str, marks: &[Mark], style: Style) -> String {
+    let guess = guess.to_uppercase();
+    match style {
+        Style::Color => {
+            let mut line = String::new();
+            for (letter, mark) in guess.chars().zip(marks) {
+                // Black letters on a green, yellow or grey background.
+                let background = match mark {
+                    Mark::Correct => 42,
+                    Mark::Present => 43,
+                    Mark::Absent => 47,
+                };
+                line.push_str(&format!("\x1b[30;{background}m {letter} \x1b[0m"));
+            }
+            line
+        }
+        Style::Symbols => {
+            let squares: String = marks
+                .iter()
+                .map(|mark| match mark {
+                    Mark::Correct => '🟩',
+                    Mark::Present => '🟨',
+                    Mark::Absent => '⬛',
+                })
+                .collect();
+            format!("{guess} {squares}")
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use guessing_game::Config;
+
+fn build(args: &str) -> Result<Config, String> {
+    Config::build(std::iter::once("guessing_game").chain(args.split_whitespace()).map(String::from))
+}
+
+#[test]
+fn modes_of_their_own_reject_what_they_ignore() {
+    for (args, error) in [
+        ("--wordle --mastermind", "--wordle does not work with --mastermind"),
+        ("--domain letter --reverse", "--domain does not work with --reverse"),
+        ("--wordle --format json", "--wordle does not work with --format"),
+        ("--mastermind --format plain", "--mastermind does not work with --format"),
+        ("--grid 5x5 --format tui", "--grid does not work with --format"),
+        ("--lies 2 --time-limit 30", "--lies does not work with --time-limit"),
+        ("--noise 0.1 --speedrun", "--noise does not work with --speedrun"),
+        ("--reverse --speedrun", "--reverse does not work with --speedrun"),
+        ("--reverse --attempts 5", "--reverse does not work with --attempts"),
+        ("--domain date --save-file round.save", "--domain does not work with --save-file"),
+        ("--wordle --fair", "--wordle does not work with --fair"),
+        ("--mastermind --evil", "--mastermind does not work with --evil"),
+        ("--grid 5x5 --evil", "--grid does not work with --evil"),
+        ("--lies 1 --fair", "--lies does not work with --fair"),
+        ("--wordle --players 2", "--wordle does not work with --players"),
+        ("--grid 5x5 --autoplay binary", "--grid does not work with --autoplay"),
+    ] {
+        assert_eq!(build(args).unwrap_err(), error, "{args}");
+    }
+}
+
+#[test]
+fn modes_of_their_own_take_what_they_support() {
+    for args in [
+        "--domain letter --evil --fair",
+        "--domain float --format human",
+        "--wordle --attempts 3 --hard --symbols",
+        "--mastermind --solve --length 5",
+        "--grid 8x8 --fair",
+        "--lies 2 --solve --attempts 20",
+        "--reverse --min 1 --max 50",
+        "--time-limit 30 --speedrun --format json",
+    ] {
+        assert!(build(args).is_ok(), "{args}: {:?}", build(args));
+    }
+}
@@ -0,0 +1,108 @@
+use guessing_game::contest::{Contest, Standing};
+use guessing_game::{Game, Outcome};
+
+fn names(count: usize) -> Vec<String> {
+    ["ann", "bob", "cy"][..count].iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn in_hot_seat_whoever_finds_the_number_wins() {
+    let mut contest = Contest::hot_seat(names(2), Game::new(1..=100, 42), None);
+    assert_eq!(contest.guess(50), Outcome::Greater);
+    assert_eq!(contest.turn(), 1);
+    assert_eq!(contest.guess(42), Outcome::Won);
+
+    assert_eq!(contest.standing(), Some(&Standing::Winner(1)));
+}
+
+#[test]
+fn in_hot_seat_the_limit_is_for_every_player() {
+    let mut contest = Contest::hot_seat(names(2), Game::new(1..=100, 42), Some(2));
+    for guess in [50, 10, 45] {
+        contest.guess(guess);
+        assert_eq!(contest.standing(), None);
+    }
+    assert_eq!(contest.attempts_remaining(0), Some(0));
+    assert_eq!(contest.attempts_remaining(1), Some(1));
+
+    // The game itself has no limit: it is the last guess of the second
+    // player that ends the contest.
+    assert_eq!(contest.guess(30), Outcome::Less);
+    assert_eq!(contest.standing(), Some(&Standing::NoWinner));
+    assert_eq!(contest.game(0).attempts(), 4);
+}
+
+#[test]
+fn a_race_waits_for_everyone_to_have_the_same_turns() {
+    let games = vec![Game::new(1..=100, 10), Game::new(1..=100, 20)];
+    let mut contest = Contest::race(names(2), games);
+
+    assert_eq!(contest.guess(10), Outcome::Won);
+    assert_eq!(contest.standing(), None);
+    assert_eq!(contest.turn(), 1);
+
+    contest.guess(50);
+    assert_eq!(contest.standing(), Some(&Standing::Winner(0)));
+    assert_eq!(contest.players()[1].attempts, 1);
+}
+
+#[test]
+fn a_race_is_won_in_the_fewest_turns_not_first() {
+    let games = vec![
+        Game::new(1..=100, 10),
+        Game::new(1..=100, 20),
+        Game::new(1..=100, 30),
+    ];
+    let mut contest = Contest::race(names(3), games);
+
+    contest.guess(50);
+    assert_eq!(contest.guess(20), Outcome::Won);
+    contest.guess(50);
+    assert_eq!(contest.standing(), Some(&Standing::Winner(1)));
+}
+
+#[test]
+fn finding_it_in_the_same_turn_is_a_tie() {
+    let games = vec![
+        Game::new(1..=100, 10),
+        Game::new(1..=100, 20),
+        Game::new(1..=100, 30),
+    ];
+    let mut contest = Contest::race(names(3), games);
+
+    for guess in [50, 50, 50, 10, 40, 30] {
+        contest.guess(guess);
+    }
+    assert_eq!(contest.standing(), Some(&Standing::Tie(vec![0, 2])));
+}
+
+#[test]
+fn a_race_nobody_wins() {
+    let games = vec![
+        Game::new(1..=100, 10).with_max_attempts(1),
+        Game::new(1..=100, 20).with_max_attempts(2),
+    ];
+    let mut contest = Contest::race(names(2), games);
+
+    contest.guess(50);
+    contest.guess(50);
+    // The first player is out, so the second goes again.
+    assert_eq!(contest.turn(), 1);
+    contest.guess(40);
+
+    assert_eq!(contest.standing(), Some(&Standing::NoWinner));
+}
+
+#[test]
+fn giving_up_decides_on_what_was_found() {
+    let mut contest = Contest::hot_seat(names(2), Game::new(1..=100, 42), None);
+    contest.guess(50);
+    contest.give_up();
+    assert_eq!(contest.standing(), Some(&Standing::NoWinner));
+
+    let games = vec![Game::new(1..=100, 10), Game::new(1..=100, 20)];
+    let mut contest = Contest::race(names(2), games);
+    contest.guess(10);
+    contest.give_up();
+    assert_eq!(contest.standing(), Some(&Standing::Winner(0)));
+}
@@ -0,0 +1,88 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use guessing_game::cli;
+use guessing_game::domain::{Date, Integers, Letters, Round, Words};
+use guessing_game::State;
+
+#[test]
+fn dates_parse_and_print_the_same() {
+    for text in ["1970-01-01", "2024-02-29", "0000-03-01", "9999-12-31"] {
+        let date: Date = text.parse().unwrap();
+        assert_eq!(date.to_string(), text);
+    }
+}
+
+#[test]
+fn impossible_dates_are_rejected() {
+    for text in [
+        "2023-02-29",
+        "2024-13-01",
+        "2024-00-10",
+        "2024-04-31",
+        "999999999999999999-01-01",
+        "2024-4294967295-01",
+        "2024-01-4294967295",
+        "-5-01-01",
+    ] {
+        assert!(text.parse::<Date>().is_err(), "{text} should not be a date");
+    }
+}
+
+#[test]
+fn domains_report_their_real_size() {
+    let error = Integers::new(i64::MIN, i64::MAX).unwrap_err();
+    assert!(error.contains("18446744073709551616 values"), "{error}");
+
+    let error = Integers::new(0, 1 << 32).unwrap_err();
+    assert!(error.contains("4294967297 values"), "{error}");
+    assert!(Integers::new(1, 1 << 32).is_ok());
+}
+
+#[test]
+fn huge_dates_typed_at_the_prompt_are_rejected() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(["--domain", "date", "--seed", "1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"999999999999999999-01-01\n2024-4294967295-01\nquit\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("'999999999999999999-01-01' is not a date"), "{stdout}");
+    assert!(stdout.contains("'2024-4294967295-01' is not a date"), "{stdout}");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn letters_and_words_that_read_like_commands_are_guesses() {
+    let mut round = Round::new(Letters::new('a', 'z').unwrap(), &'q');
+    let state = cli::play_domain(&mut round, "m\nq\n".as_bytes(), Vec::new()).unwrap();
+    assert_eq!(state, State::Won);
+    assert_eq!(round.history().len(), 2);
+
+    let words = Words::from_list("exit\nhelp\nhint\nhistory\nquit\nsave").unwrap();
+    let mut round = Round::new(words, &"quit".to_string());
+    let state = cli::play_domain(&mut round, "help\nhistory\nquit\n".as_bytes(), Vec::new()).unwrap();
+    assert_eq!(state, State::Won);
+    assert_eq!(round.history().len(), 3);
+}
+
+#[test]
+fn commands_still_work_when_they_are_no_guess() {
+    let mut round = Round::new(Letters::new('a', 'z').unwrap(), &'q');
+    let mut output = Vec::new();
+    let state = cli::play_domain(&mut round, "help\nquit\n".as_bytes(), &mut output).unwrap();
+    assert_eq!(state, State::GaveUp);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Type a letter to guess it"), "{output}");
+    assert!(output.contains("The secret letter was q."), "{output}");
+}
@@ -0,0 +1,81 @@
+use std::ops::RangeInclusive;
+
+use guessing_game::game::fair_attempts;
+use guessing_game::strategy::{BinarySearch, LinearScan, Pessimistic};
+use guessing_game::{Game, State, Strategy};
+
+const RANGES: [RangeInclusive<u32>; 8] = [
+    1..=1,
+    1..=2,
+    1..=3,
+    1..=10,
+    1..=100,
+    0..=1023,
+    7..=1000,
+    u32::MAX - 500..=u32::MAX,
+];
+
+// Plays `strategy` against the evil host until it wins, checking after every
+// answer that some secret number would have given all the answers so far.
+fn play_evil(range: RangeInclusive<u32>, strategy: &mut dyn Strategy) -> Game {
+    let mut game = Game::evil(range.clone());
+
+    while game.state() == State::Playing {
+        let guess = strategy.next_guess();
+        let outcome = game.guess(guess);
+        strategy.feedback(guess, outcome.ordering());
+
+        let candidates = game.candidates();
+        assert!(!candidates.is_empty(), "{range:?}: no secret is left after guessing {guess}");
+        assert!(
+            game.is_consistent(game.secret_number()),
+            "{range:?}: {} contradicts the answers {:?}",
+            game.secret_number(),
+            game.history()
+        );
+        // Every candidate left is a secret the host could still reveal.
+        assert!(game.is_consistent(candidates.low()));
+        assert!(game.is_consistent(candidates.high()));
+
+        assert!(game.attempts() <= 1 << 12, "{range:?}: the game never ends");
+    }
+
+    assert_eq!(game.state(), State::Won);
+    game
+}
+
+#[test]
+fn binary_search_needs_exactly_the_fair_number_of_guesses() {
+    for range in RANGES.into_iter().chain([0..=u32::MAX]) {
+        let game = play_evil(range.clone(), &mut BinarySearch::new(&range));
+        assert_eq!(game.attempts(), fair_attempts(&range), "{range:?}");
+    }
+}
+
+#[test]
+fn linear_scan_has_to_try_every_number() {
+    for range in RANGES {
+        let game = play_evil(range.clone(), &mut LinearScan::new(&range));
+        assert_eq!(u64::from(game.attempts()), u64::from(range.end() - range.start()) + 1, "{range:?}");
+    }
+}
+
+#[test]
+fn the_pessimist_has_to_try_every_number() {
+    for range in RANGES {
+        let game = play_evil(range.clone(), &mut Pessimistic::new(&range));
+        assert_eq!(u64::from(game.attempts()), u64::from(range.end() - range.start()) + 1, "{range:?}");
+    }
+}
+
+#[test]
+fn an_honest_game_is_consistent_with_its_secret_only() {
+    let mut game = Game::new(1..=100, 42);
+    for guess in [50, 25, 41, 43] {
+        game.guess(guess);
+        assert!(game.is_consistent(42));
+    }
+    assert!(!game.is_consistent(41));
+    assert!(!game.is_consistent(44));
+    assert!(!game.is_consistent(101));
+}
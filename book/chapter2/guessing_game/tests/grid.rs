@@ -0,0 +1,30 @@
+use guessing_game::grid::{Answer, Clues, Grid, Point, Size};
+
+fn bounds(grid: &Grid) -> Vec<(u32, u32)> {
+    grid.candidates().iter().map(|axis| (axis.low(), axis.high())).collect()
+}
+
+#[test]
+fn directions_narrow_every_axis_at_once() {
+    let mut grid = Grid::new(Size(vec![10, 10]), Point(vec![2, 7]), Clues::Directions);
+    assert_eq!(bounds(&grid), [(0, 9), (0, 9)]);
+
+    grid.guess(&Point(vec![5, 5]));
+    assert_eq!(bounds(&grid), [(0, 4), (6, 9)]);
+
+    // A coordinate that is right already pins its axis down.
+    grid.guess(&Point(vec![2, 8]));
+    assert_eq!(bounds(&grid), [(2, 2), (6, 7)]);
+
+    assert_eq!(grid.guess(&Point(vec![2, 7])), Answer::Found);
+    assert_eq!(bounds(&grid), [(2, 2), (7, 7)]);
+}
+
+#[test]
+fn warmth_leaves_the_whole_grid_possible() {
+    let mut grid = Grid::new(Size(vec![10, 10]), Point(vec![2, 7]), Clues::Warmth);
+
+    grid.guess(&Point(vec![5, 5]));
+    grid.guess(&Point(vec![3, 6]));
+    assert_eq!(bounds(&grid), [(0, 9), (0, 9)]);
+}
@@ -0,0 +1,122 @@
+use guessing_game::hint::{Clue, Hint, Points, GUESS_COST, START_POINTS};
+use guessing_game::Game;
+
+// The quarter and its bounds for every number of `min..=max`.
+fn quarters(min: u32, max: u32) -> Vec<(u32, u32, u32)> {
+    (min..=max)
+        .map(|number| match Hint::Quarter.reveal(&(min..=max), number) {
+            Clue::Quarter { quarter, low, high } => (quarter, low, high),
+            clue => panic!("{clue:?} is no quarter"),
+        })
+        .collect()
+}
+
+#[test]
+fn small_ranges_have_quarters_of_one_number_or_none() {
+    assert_eq!(quarters(1, 3), [(1, 1, 1), (2, 2, 2), (3, 3, 3)]);
+    assert_eq!(quarters(7, 7), [(1, 7, 7)]);
+    assert_eq!(quarters(0, 1), [(1, 0, 0), (3, 1, 1)]);
+    assert_eq!(quarters(1, 5), [(1, 1, 2), (1, 1, 2), (2, 3, 3), (3, 4, 4), (4, 5, 5)]);
+}
+
+#[test]
+fn quarters_split_every_range_evenly_and_contain_the_number() {
+    for min in 0..4 {
+        for max in min..min + 30 {
+            let quarters = quarters(min, max);
+            for (number, &(quarter, low, high)) in (min..).zip(&quarters) {
+                assert!((1..=4).contains(&quarter), "{min}-{max}: {number} is in quarter {quarter}");
+                assert!(low <= number && number <= high, "{min}-{max}: {number} is not in {low}-{high}");
+            }
+
+            // The quarters follow each other without gaps, and none is more
+            // than one number longer than another. Ranges of fewer than four
+            // numbers leave some quarters empty, so the count may skip.
+            let mut bounds = quarters.clone();
+            bounds.dedup();
+            for pair in bounds.windows(2) {
+                assert!(pair[0].0 < pair[1].0, "{min}-{max}: {bounds:?}");
+                assert_eq!(pair[0].2 + 1, pair[1].1, "{min}-{max}: {bounds:?}");
+            }
+            let lengths: Vec<u32> = bounds.iter().map(|&(_, low, high)| high - low + 1).collect();
+            let (shortest, longest) = (lengths.iter().min().unwrap(), lengths.iter().max().unwrap());
+            assert!(longest - shortest <= 1, "{min}-{max}: {bounds:?}");
+        }
+    }
+}
+
+#[test]
+fn halves_and_quarters_work_on_the_whole_u32_range() {
+    let range = 0..=u32::MAX;
+    assert_eq!(
+        Hint::Quarter.reveal(&range, u32::MAX),
+        Clue::Quarter {
+            quarter: 4,
+            low: 3 << 30,
+            high: u32::MAX
+        }
+    );
+    assert_eq!(
+        Hint::Half.reveal(&range, 0),
+        Clue::Half {
+            half: 1,
+            low: 0,
+            high: (1 << 31) - 1
+        }
+    );
+}
+
+#[test]
+fn guesses_and_hints_are_paid_for_out_of_the_points() {
+    let points = Points {
+        guesses: 4,
+        hints: vec![Hint::Parity],
+        won: true,
+    };
+    assert_eq!(points.guess_cost(), 4 * GUESS_COST);
+    assert_eq!(points.hint_cost(), 10);
+    assert_eq!(points.total(), 70);
+    assert_eq!(points.to_string(), "100 - 4 guesses x 5 - parity hint 10 = 70 points");
+
+    let points = Points {
+        guesses: 1,
+        hints: vec![Hint::Quarter, Hint::DigitSum, Hint::Divisible(7)],
+        won: true,
+    };
+    assert_eq!(points.total(), START_POINTS - 5 - 20 - 25 - 10);
+    assert_eq!(
+        points.to_string(),
+        "100 - 1 guess x 5 - quarter hint 20 - digits hint 25 - div7 hint 10 = 40 points"
+    );
+}
+
+#[test]
+fn points_never_go_below_nothing() {
+    let points = Points {
+        guesses: 15,
+        hints: vec![Hint::DigitSum, Hint::Quarter],
+        won: true,
+    };
+    assert_eq!(points.total(), 0);
+
+    let lost = Points {
+        guesses: 1,
+        hints: Vec::new(),
+        won: false,
+    };
+    assert_eq!(lost.total(), 0);
+    assert_eq!(lost.to_string(), "100 - 1 guess x 5, but only a won round scores: 0 points");
+}
+
+#[test]
+fn a_game_charges_for_the_hints_it_gave() {
+    let mut game = Game::new(1..=100, 42);
+    game.hint(None).unwrap();
+    game.hint(Some(Hint::Quarter)).unwrap();
+    game.guess(50);
+    game.guess(42);
+
+    let points = game.points();
+    assert_eq!(points.hints, [Hint::Parity, Hint::Quarter]);
+    assert_eq!(points.total(), 100 - 2 * 5 - 10 - 20);
+}
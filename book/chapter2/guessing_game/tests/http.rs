@@ -0,0 +1,202 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use serde_json::{json, Value};
+
+use guessing_game::http::{self, Store};
+
+fn start_server(expiry: Duration) -> SocketAddr {
+    start_server_with(Arc::new(Store::new(1..=100, expiry)))
+}
+
+fn start_server_with(store: Arc<Store>) -> SocketAddr {
+    start_server_timing_out(store, http::REQUEST_TIMEOUT)
+}
+
+fn start_server_timing_out(store: Arc<Store>, timeout: Duration) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || http::serve(listener, store, timeout));
+    address
+}
+
+// Sends `request` as it is and reads the status of the response.
+fn send_raw(address: SocketAddr, request: &[u8]) -> u16 {
+    let mut stream = TcpStream::connect(address).unwrap();
+    // The server may stop reading and close early, which is fine.
+    let _ = stream.write_all(request);
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response[9..12].parse().unwrap()
+}
+
+fn request(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn play_a_game_to_the_end() {
+    let address = start_server(Duration::from_secs(60));
+
+    let (status, game) = request(address, "POST", "/games", r#"{"min":1,"max":100,"seed":7}"#);
+    assert_eq!(status, 201);
+    assert_eq!(game["state"], "playing");
+    assert_eq!(game["secret"], Value::Null);
+    let id = game["id"].as_u64().unwrap();
+
+    // Binary search is bound to find it within seven guesses.
+    let (mut low, mut high) = (1, 100);
+    for attempt in 1..=7 {
+        let guess = (low + high) / 2;
+        let path = format!("/games/{id}/guesses");
+        let (status, answer) = request(address, "POST", &path, &json!({ "guess": guess }).to_string());
+        assert_eq!(status, 200);
+        assert_eq!(answer["attempt"], attempt);
+
+        match answer["result"].as_str().unwrap() {
+            "less" => low = guess + 1,
+            "greater" => high = guess - 1,
+            "won" => break,
+            result => panic!("unexpected result {result}"),
+        }
+    }
+
+    let (status, game) = request(address, "GET", &format!("/games/{id}"), "");
+    assert_eq!(status, 200);
+    assert_eq!(game["state"], "won");
+    assert_eq!(game["secret"], game["history"].as_array().unwrap().last().unwrap()["guess"]);
+
+    let (status, _) = request(address, "POST", &format!("/games/{id}/guesses"), r#"{"guess":1}"#);
+    assert_eq!(status, 409);
+}
+
+#[test]
+fn same_seed_gives_same_game() {
+    let address = start_server(Duration::from_secs(60));
+
+    let mut answers = Vec::new();
+    for _ in 0..2 {
+        let (_, game) = request(address, "POST", "/games", r#"{"seed":3,"max_attempts":1}"#);
+        let path = format!("/games/{}/guesses", game["id"]);
+        let (_, answer) = request(address, "POST", &path, r#"{"guess":50}"#);
+        answers.push(answer["game"]["secret"].clone());
+    }
+
+    assert_eq!(answers[0], answers[1]);
+    assert_ne!(answers[0], Value::Null);
+}
+
+#[test]
+fn bad_requests_are_rejected() {
+    let address = start_server(Duration::from_secs(60));
+
+    assert_eq!(request(address, "POST", "/games", "{").0, 400);
+    assert_eq!(request(address, "POST", "/games", r#"{"min":10,"max":1}"#).0, 400);
+    assert_eq!(request(address, "GET", "/games/42", "").0, 404);
+    assert_eq!(request(address, "DELETE", "/games/1", "").0, 405);
+    assert_eq!(request(address, "GET", "/nothing", "").0, 404);
+
+    let (_, game) = request(address, "POST", "/games", "");
+    let path = format!("/games/{}/guesses", game["id"]);
+    assert_eq!(request(address, "POST", &path, r#"{"guess":"fifty"}"#).0, 400);
+}
+
+#[test]
+fn games_expire() {
+    let address = start_server(Duration::from_millis(100));
+
+    let (_, game) = request(address, "POST", "/games", "");
+    let path = format!("/games/{}", game["id"]);
+    assert_eq!(request(address, "GET", &path, "").0, 200);
+
+    thread::sleep(Duration::from_millis(250));
+    assert_eq!(request(address, "GET", &path, "").0, 404);
+}
+
+#[test]
+fn guesses_outside_of_the_range_cost_nothing() {
+    let address = start_server(Duration::from_secs(60));
+
+    let (_, game) = request(address, "POST", "/games", r#"{"min":1,"max":10,"seed":3}"#);
+    let path = format!("/games/{}/guesses", game["id"]);
+    for guess in [0, 11, 5000] {
+        let (status, error) = request(address, "POST", &path, &json!({ "guess": guess }).to_string());
+        assert_eq!(status, 422);
+        assert_eq!(error["error"], "the guess must be between 1 and 10");
+    }
+
+    let (status, answer) = request(address, "POST", &path, r#"{"guess":5}"#);
+    assert_eq!(status, 200);
+    assert_eq!(answer["attempt"], 1);
+}
+
+#[test]
+fn creating_games_purges_expired_ones() {
+    let store = Arc::new(Store::new(1..=100, Duration::from_millis(100)));
+    let address = start_server_with(Arc::clone(&store));
+
+    for _ in 0..5 {
+        request(address, "POST", "/games", "");
+    }
+    assert_eq!(store.count(), 5);
+
+    thread::sleep(Duration::from_millis(250));
+    request(address, "POST", "/games", "");
+    assert_eq!(store.count(), 1);
+}
+
+#[test]
+fn a_request_sent_byte_by_byte_still_times_out() {
+    let store = Arc::new(Store::new(1..=100, Duration::from_secs(60)));
+    let address = start_server_timing_out(store, Duration::from_millis(300));
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    // Every byte comes well within the timeout, but the headers never end.
+    for byte in b"GET /games/1 HTTP/1.1\r\nX-Slow: ".iter().chain(b"aaaaaaaaaaaaaaaaaaaa") {
+        if stream.write_all(&[*byte]).is_err() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout"), "{response}");
+}
+
+#[test]
+fn long_lines_and_too_many_headers_are_refused() {
+    let address = start_server(Duration::from_secs(60));
+    let long = "a".repeat(http::MAX_LINE as usize * 2);
+
+    let request = format!("GET /{long} HTTP/1.1\r\n\r\n");
+    assert_eq!(send_raw(address, request.as_bytes()), 414);
+
+    let request = format!("GET /games/1 HTTP/1.1\r\nX-Long: {long}\r\n\r\n");
+    assert_eq!(send_raw(address, request.as_bytes()), 431);
+
+    let headers = "X-Many: yes\r\n".repeat(http::MAX_HEADERS + 1);
+    let request = format!("GET /games/1 HTTP/1.1\r\n{headers}\r\n");
+    assert_eq!(send_raw(address, request.as_bytes()), 431);
+
+    // Right at the limit is still fine.
+    let headers = "X-Many: yes\r\n".repeat(http::MAX_HEADERS);
+    let request = format!("GET /games/1 HTTP/1.1\r\n{headers}\r\n");
+    assert_eq!(send_raw(address, request.as_bytes()), 404);
+}
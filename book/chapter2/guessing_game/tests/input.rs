@@ -0,0 +1,76 @@
+use guessing_game::hint::Hint;
+use guessing_game::input::{self, InputError, Line};
+
+#[test]
+fn lines_parse_to_guesses_commands_or_errors() {
+    let out_of_range = |guess| InputError::OutOfRange { guess, min: 1, max: 5000 };
+
+    let cases = [
+        ("42", Ok(Line::Guess(42))),
+        ("  42 \n", Ok(Line::Guess(42))),
+        ("+5", Ok(Line::Guess(5))),
+        ("1_000", Ok(Line::Guess(1000))),
+        ("1,000", Ok(Line::Guess(1000))),
+        ("4,9_99", Ok(Line::Guess(4999))),
+        ("42.0", Ok(Line::Guess(42))),
+        ("42.", Ok(Line::Guess(42))),
+        ("007", Ok(Line::Guess(7))),
+        // Minus zero is no negative number, only outside of the range.
+        ("-0", Err(out_of_range(0))),
+        ("-0.0", Err(out_of_range(0))),
+        ("0", Err(out_of_range(0))),
+        ("5001", Err(out_of_range(5001))),
+        ("4294967295", Err(out_of_range(u32::MAX))),
+        ("4294967296", Err(InputError::Overflow)),
+        ("99999999999999999999", Err(InputError::Overflow)),
+        ("-5", Err(InputError::Negative)),
+        ("-5.5", Err(InputError::Negative)),
+        ("42.5", Err(InputError::NotWhole)),
+        ("42.01", Err(InputError::NotWhole)),
+        ("", Err(InputError::Empty)),
+        ("   \n", Err(InputError::Empty)),
+        ("abc", Err(InputError::NotANumber)),
+        ("12abc", Err(InputError::NotANumber)),
+        ("0x10", Err(InputError::NotANumber)),
+        ("1.5e3", Err(InputError::NotANumber)),
+        ("+-5", Err(InputError::NotANumber)),
+        ("1__000", Err(InputError::NotANumber)),
+        ("_1000", Err(InputError::NotANumber)),
+        ("1000,", Err(InputError::NotANumber)),
+        ("1 2", Err(InputError::Several)),
+        ("1, 2", Err(InputError::Several)),
+        ("1 two", Err(InputError::NotANumber)),
+        ("quit", Ok(Line::Quit)),
+        ("Q", Ok(Line::Quit)),
+        ("EXIT\n", Ok(Line::Quit)),
+        ("hint", Ok(Line::Hint(None))),
+        ("hint half", Ok(Line::Hint(Some(Hint::Half)))),
+        ("HINT div7", Ok(Line::Hint(Some(Hint::Divisible(7))))),
+        ("hint nope", Err(InputError::UnknownHint("nope".to_string()))),
+        ("history", Ok(Line::History)),
+        ("help", Ok(Line::Help)),
+        ("?", Ok(Line::Help)),
+        ("save", Ok(Line::Save)),
+    ];
+
+    for (line, expected) in cases {
+        assert_eq!(input::parse(line, &(1..=5000)), expected, "{line:?}");
+    }
+}
+
+#[test]
+fn every_error_has_a_name_and_a_message() {
+    for error in [
+        InputError::Empty,
+        InputError::NotANumber,
+        InputError::Negative,
+        InputError::NotWhole,
+        InputError::Several,
+        InputError::Overflow,
+        InputError::OutOfRange { guess: 0, min: 1, max: 100 },
+        InputError::UnknownHint("nope".to_string()),
+    ] {
+        assert!(!error.name().is_empty());
+        assert!(error.to_string().ends_with('.'), "{error}");
+    }
+}
@@ -0,0 +1,57 @@
+use guessing_game::liar::{query_bound, Noise, MAX_LIES};
+use guessing_game::Config;
+
+// The volume bound worked out with whole numbers, for games short enough.
+fn exact_bound(size: u128, k: u32) -> u32 {
+    (0..120)
+        .find(|&q| {
+            let mut binomial = 1u128;
+            let mut volume = 1u128;
+            for j in 1..=k.min(q) {
+                binomial = binomial * u128::from(q - j + 1) / u128::from(j);
+                volume += binomial;
+            }
+            1u128 << q >= size * volume
+        })
+        .unwrap()
+}
+
+#[test]
+fn the_volume_bound_matches_whole_numbers() {
+    assert_eq!(query_bound(100, Noise::Limit(0)), Some(7));
+    assert_eq!(query_bound(1, Noise::Limit(3)), Some(0));
+    // Pelc: a million numbers and one lie take 25 questions.
+    assert_eq!(query_bound(1_000_000, Noise::Limit(1)), Some(25));
+
+    for size in [1, 2, 3, 10, 100, 1000, 1 << 20] {
+        for k in 0..=8 {
+            assert_eq!(query_bound(size, Noise::Limit(k)), Some(exact_bound(size.into(), k)), "{size} {k}");
+        }
+    }
+}
+
+#[test]
+fn many_lies_do_not_overflow_the_bound() {
+    let bound = query_bound(10, Noise::Limit(MAX_LIES)).unwrap();
+    // Each lie costs at least two questions more.
+    assert!(bound > 2 * MAX_LIES, "{bound}");
+
+    let bound = query_bound(u64::from(u32::MAX) + 1, Noise::Limit(10_000)).unwrap();
+    assert!(bound > 20_000, "{bound}");
+}
+
+#[test]
+fn the_noisy_bound_grows_with_the_noise() {
+    assert_eq!(query_bound(1024, Noise::Chance(0.0)), Some(10));
+    let low = query_bound(1024, Noise::Chance(0.1)).unwrap();
+    let high = query_bound(1024, Noise::Chance(0.3)).unwrap();
+    assert!(10 < low && low < high, "{low} {high}");
+    assert_eq!(query_bound(1024, Noise::Chance(0.5)), None);
+}
+
+#[test]
+fn lies_are_limited() {
+    let args = |lies: u32| ["guessing_game".to_string(), "--lies".to_string(), lies.to_string()].into_iter();
+    assert!(Config::build(args(MAX_LIES)).is_ok());
+    assert!(Config::build(args(MAX_LIES + 1)).is_err());
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use guessing_game::mastermind::{Code, Feedback, Knuth, Rules, Symbols};
+
+fn classic() -> Rules {
+    Rules::new(4, 6, true, Symbols::Digits).unwrap()
+}
+
+fn code(text: &str) -> Code {
+    classic().parse(text).unwrap()
+}
+
+#[test]
+fn repeated_symbols_are_only_counted_as_often_as_they_match() {
+    for (guess, secret, exact, partial) in [
+        ("5555", "5555", 4, 0),
+        ("1100", "0011", 0, 4),
+        ("2211", "1122", 0, 4),
+        // The extra zeros have nothing left to match.
+        ("0000", "0011", 2, 0),
+        ("0000", "0123", 1, 0),
+        ("1111", "1234", 1, 0),
+        ("2111", "1222", 0, 2),
+        ("0120", "0012", 1, 3),
+        ("0011", "1000", 1, 2),
+        ("3345", "0123", 0, 1),
+    ] {
+        assert_eq!(
+            Feedback::compare(&code(guess), &code(secret)),
+            Feedback { exact, partial },
+            "{guess} against {secret}"
+        );
+    }
+}
+
+#[test]
+fn feedback_is_the_same_both_ways() {
+    let codes = classic().all_codes();
+    for a in &codes {
+        for b in &codes {
+            let feedback = Feedback::compare(a, b);
+            assert_eq!(feedback, Feedback::compare(b, a));
+            assert!(feedback.exact + feedback.partial <= 4);
+            assert_eq!(feedback.exact == 4, a == b);
+        }
+    }
+}
+
+#[test]
+fn knuth_cracks_every_classic_code_in_five_guesses() {
+    let rules = classic();
+    // Games that got the same answers so far make the same next guess, so
+    // it is only worked out once.
+    let mut next_guesses: HashMap<Vec<Feedback>, Code> = HashMap::new();
+    let mut most = 0;
+
+    for secret in rules.all_codes() {
+        let mut solver = Knuth::new(rules);
+        let mut answers = Vec::new();
+
+        loop {
+            let guess = next_guesses
+                .entry(answers.clone())
+                .or_insert_with(|| solver.next_guess())
+                .clone();
+            let feedback = Feedback::compare(&guess, &secret);
+            answers.push(feedback);
+            if feedback.exact == 4 {
+                break;
+            }
+            assert!(answers.len() < 5, "{:?} is still not cracked", rules.display(&secret).to_string());
+            solver.feedback(&guess, feedback);
+        }
+
+        most = most.max(answers.len());
+    }
+
+    assert_eq!(most, 5);
+}
@@ -0,0 +1,158 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use guessing_game::net;
+use guessing_game::Game;
+
+// Serves games on a free loopback port. The secret number of connection `n`
+// is 42 + n, so that games running at once can be told apart.
+fn start_server(idle_timeout: Duration) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let new_game = |number| Game::new(1..=100, 42 + number as u32).with_max_attempts(10);
+    thread::spawn(move || net::serve(listener, new_game, idle_timeout));
+    address
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(address: SocketAddr) -> Client {
+        let stream = TcpStream::connect(address).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        let mut client = Client {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: stream,
+        };
+        assert_eq!(client.read(), "RANGE 1 100");
+        client
+    }
+
+    fn send(&mut self, line: &str) -> String {
+        writeln!(self.writer, "{line}").unwrap();
+        self.read()
+    }
+
+    fn read(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    // Binary search over the protocol, returning the final answer.
+    fn play(&mut self) -> String {
+        let (mut low, mut high) = (1, 100);
+        loop {
+            let guess = (low + high) / 2;
+            match self.send(&format!("GUESS {guess}")).as_str() {
+                "LESS" => low = guess + 1,
+                "GREATER" => high = guess - 1,
+                answer => return answer.to_string(),
+            }
+        }
+    }
+}
+
+#[test]
+fn a_game_is_won_over_loopback() {
+    let address = start_server(Duration::from_secs(10));
+    let mut client = Client::connect(address);
+
+    assert_eq!(client.send("GUESS 50"), "GREATER");
+    assert_eq!(client.send("GUESS 25"), "LESS");
+    assert_eq!(client.send("GUESS 42"), "WIN 3");
+
+    // The server closes the connection after the game.
+    assert_eq!(client.read(), "");
+}
+
+#[test]
+fn malformed_lines_get_an_error_and_cost_nothing() {
+    let address = start_server(Duration::from_secs(10));
+    let mut client = Client::connect(address);
+
+    assert_eq!(client.send("GUESS fifty"), "ERROR 'fifty' is not a number");
+    assert_eq!(client.send("HELLO"), "ERROR unknown request 'HELLO'");
+    assert_eq!(client.send(""), "ERROR empty line");
+    assert_eq!(client.send("GUESS 0"), "ERROR 0 is outside of 1-100");
+    assert_eq!(client.send("GUESS 5000"), "ERROR 5000 is outside of 1-100");
+    assert_eq!(client.send("GUESS 42"), "WIN 1");
+}
+
+#[test]
+fn quitting_reveals_the_secret() {
+    let address = start_server(Duration::from_secs(10));
+    let mut client = Client::connect(address);
+
+    assert_eq!(client.send("QUIT"), "BYE 42");
+}
+
+#[test]
+fn an_idle_client_gets_a_timeout() {
+    let address = start_server(Duration::from_millis(200));
+    let mut client = Client::connect(address);
+
+    assert_eq!(client.read(), "TIMEOUT");
+}
+
+#[test]
+fn a_line_sent_byte_by_byte_still_times_out() {
+    let address = start_server(Duration::from_millis(300));
+    let mut client = Client::connect(address);
+
+    // Every byte comes well within the timeout, but the line never ends.
+    for _ in 0..10 {
+        if client.writer.write_all(b"G").is_err() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(client.read(), "TIMEOUT");
+}
+
+#[test]
+fn a_line_that_is_too_long_closes_the_connection() {
+    let address = start_server(Duration::from_secs(10));
+    let mut client = Client::connect(address);
+
+    let line = "9".repeat(net::MAX_LINE as usize * 4);
+    assert_eq!(client.send(&line), format!("ERROR the line is longer than {} bytes", net::MAX_LINE));
+    assert_eq!(client.read(), "");
+}
+
+#[test]
+fn two_clients_play_at_once() {
+    let address = start_server(Duration::from_secs(10));
+    let mut first = Client::connect(address);
+    let mut second = Client::connect(address);
+
+    // Interleave the games to show neither waits for the other.
+    assert_eq!(first.send("GUESS 50"), "GREATER");
+    assert_eq!(second.send("GUESS 50"), "GREATER");
+
+    let answers = [first.play(), second.play()];
+    assert!(answers.iter().all(|answer| answer.starts_with("WIN")), "{answers:?}");
+
+    // Every connection plays its own game, with its own secret.
+    let mut third = Client::connect(address);
+    assert_eq!(third.send("QUIT"), "BYE 44");
+}
+
+#[test]
+fn the_client_plays_against_the_server() {
+    let address = start_server(Duration::from_secs(10));
+    let stream = TcpStream::connect(address).unwrap();
+
+    let mut output = Vec::new();
+    net::connect(stream, "abc\n50\n0\n42\n".as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("Guessing the number between 1 and 100!"), "{output}");
+    assert!(output.contains("Too big guess!"), "{output}");
+    assert!(output.contains("You won in 2 attempts!"), "{output}");
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+use std::time::Duration;
+
+use guessing_game::report::{Json, Plain, Reporter};
+
+// The rest of the plain and JSON output is checked by the golden transcripts; these
+// depend on the time or on a file being written.
+#[test]
+fn plain_splits_and_saves_are_one_line_each() {
+    let mut output = Vec::new();
+    let mut plain = Plain::new(&mut output);
+    plain.saved(Path::new("my round.save")).unwrap();
+    plain
+        .splits(&[Duration::from_millis(1500), Duration::from_millis(4250)], Duration::from_millis(5000))
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "saved path=\"my round.save\"\n\
+         split attempt=1 at_ms=1500\n\
+         split attempt=2 at_ms=4250\n\
+         total_ms=5000\n"
+    );
+}
+
+#[test]
+fn json_splits_and_saves_are_one_object_each() {
+    let mut output = Vec::new();
+    let mut json = Json::new(&mut output);
+    json.saved(Path::new("my round.save")).unwrap();
+    json.splits(&[Duration::from_millis(1500)], Duration::from_millis(2000)).unwrap();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            serde_json::json!({ "saved": "my round.save" }),
+            serde_json::json!({ "splits_ms": [1500], "total_ms": 2000 }),
+        ]
+    );
+}
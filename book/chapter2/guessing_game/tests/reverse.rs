@@ -0,0 +1,85 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use guessing_game::reverse::{Answer, Contradiction, Reverse};
+
+// Whether `secret` would have been given `answer` to `guess`.
+fn fits(secret: u32, guess: u32, answer: Answer) -> bool {
+    guess.cmp(&secret) == answer.ordering()
+}
+
+#[test]
+fn an_answer_no_number_fits_blames_the_range() {
+    let mut reverse = Reverse::new(1..=100);
+
+    assert_eq!(reverse.answer(100, Answer::Higher), Err(Contradiction::Range));
+    assert_eq!(reverse.answer(1, Answer::Lower), Err(Contradiction::Range));
+    assert_eq!(reverse.answer(101, Answer::Correct), Err(Contradiction::Range));
+    assert!(reverse.answers().is_empty());
+}
+
+#[test]
+fn a_contradiction_blames_the_earlier_answer() {
+    let mut reverse = Reverse::new(1..=100);
+    reverse.answer(50, Answer::Higher).unwrap();
+    reverse.answer(75, Answer::Lower).unwrap();
+    reverse.answer(62, Answer::Higher).unwrap();
+    let candidates = reverse.candidates();
+
+    // Only "62 is too small" rules out anything below 60.
+    assert_eq!(reverse.answer(60, Answer::Lower), Err(Contradiction::Answer(2)));
+    // Only "75 is too big" rules out 80.
+    assert_eq!(reverse.answer(80, Answer::Correct), Err(Contradiction::Answer(1)));
+    // Both 50 and 62 rule out 40; the latest answer is blamed.
+    assert_eq!(reverse.answer(40, Answer::Correct), Err(Contradiction::Answer(2)));
+
+    // A contradicted answer changes nothing.
+    assert_eq!(reverse.answers().len(), 3);
+    assert_eq!(reverse.candidates(), candidates);
+
+    reverse.answer(70, Answer::Lower).unwrap();
+    assert_eq!(reverse.answer(70, Answer::Higher), Err(Contradiction::Answer(3)));
+}
+
+// Throws random answers at random guesses and checks every verdict against
+// brute force: an accepted answer leaves some number that fits everything,
+// and a contradiction is between the new answer and the one it blames.
+#[test]
+fn contradictions_always_have_a_single_answer_to_blame() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let answers = [Answer::Higher, Answer::Lower, Answer::Correct];
+
+    for _ in 0..500 {
+        let range = 1..=rng.gen_range(1..=20);
+        let mut reverse = Reverse::new(range.clone());
+
+        for _ in 0..10 {
+            let guess = rng.gen_range(0..=21);
+            let answer = answers[rng.gen_range(0..answers.len())];
+
+            match reverse.answer(guess, answer) {
+                Ok(()) => {
+                    let left = range
+                        .clone()
+                        .filter(|&secret| reverse.answers().iter().all(|&(g, a)| fits(secret, g, a)))
+                        .count();
+                    assert!(left > 0, "no number fits {:?}", reverse.answers());
+                    assert_eq!(left as u64, reverse.candidates().len());
+                }
+                Err(Contradiction::Range) => {
+                    assert!(!range.clone().any(|secret| fits(secret, guess, answer)));
+                }
+                Err(Contradiction::Answer(index)) => {
+                    let (earlier_guess, earlier_answer) = reverse.answers()[index];
+                    assert!(
+                        !range
+                            .clone()
+                            .any(|secret| fits(secret, guess, answer) && fits(secret, earlier_guess, earlier_answer)),
+                        "{guess} {answer} does not contradict {earlier_guess} {earlier_answer}"
+                    );
+                    assert!(range.clone().any(|secret| fits(secret, guess, answer)));
+                }
+            }
+        }
+    }
+}
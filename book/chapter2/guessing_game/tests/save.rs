@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use guessing_game::hint::Hint;
+use guessing_game::save::{SaveTarget, SavedGame, Secret};
+use guessing_game::{Game, Host, State};
+
+// Saves `game` and reads it back, the way `save` and `--resume` do.
+fn round_trip(game: &Game, obfuscate: bool) -> (SavedGame, String) {
+    let path = temporary_file(&format!("round-trip-{obfuscate}-{}", game.attempts()));
+    let target = SaveTarget {
+        path: path.clone(),
+        obfuscate,
+    };
+    target.save(game, Duration::from_millis(12_345)).unwrap();
+
+    let contents = fs::read_to_string(&path).unwrap();
+    let saved = SavedGame::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    (saved, contents)
+}
+
+fn temporary_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("guessing-game-save-{}-{name}", std::process::id()))
+}
+
+fn assert_same(restored: &Game, game: &Game) {
+    assert_eq!(restored.range(), game.range());
+    assert_eq!(restored.host(), game.host());
+    assert_eq!(restored.secret_number(), game.secret_number());
+    assert_eq!(restored.history(), game.history());
+    assert_eq!(restored.clues(), game.clues());
+    assert_eq!(restored.max_attempts(), game.max_attempts());
+    assert_eq!(restored.state(), game.state());
+    assert_eq!(restored.candidates(), game.candidates());
+}
+
+#[test]
+fn a_plain_round_comes_back_the_same() {
+    let mut game = Game::new(1..=100, 42);
+    game.guess(50);
+    game.guess(25);
+
+    let (saved, _) = round_trip(&game, false);
+    assert_eq!(saved.secret, Secret::Plain(42));
+    assert_eq!(saved.elapsed(), Duration::from_millis(12_345));
+    assert_same(&saved.restore().unwrap(), &game);
+}
+
+#[test]
+fn an_obfuscated_secret_is_not_in_the_file() {
+    let mut game = Game::new(1..=100_000, 31_337);
+    game.guess(50_000);
+
+    let (saved, contents) = round_trip(&game, true);
+    assert!(matches!(saved.secret, Secret::Obfuscated(_)));
+    assert!(!contents.contains("31337"), "{contents}");
+    assert!(!contents.contains(&format!("{:x}", 31_337)), "{contents}");
+    assert_same(&saved.restore().unwrap(), &game);
+}
+
+#[test]
+fn an_evil_round_keeps_every_answer() {
+    let mut game = Game::evil(1..=100);
+    for guess in [50, 75, 60] {
+        game.guess(guess);
+    }
+
+    let (saved, _) = round_trip(&game, true);
+    assert_eq!(saved.secret, Secret::Evil);
+    let restored = saved.restore().unwrap();
+    assert_eq!(restored.host(), Host::Evil);
+    assert_same(&restored, &game);
+}
+
+#[test]
+fn hints_and_guesses_come_back_in_order() {
+    let mut game = Game::new(1..=100, 42);
+    game.hint(Some(Hint::Parity)).unwrap();
+    game.guess(50);
+    game.hint(Some(Hint::Quarter)).unwrap();
+    game.guess(30);
+
+    let (saved, _) = round_trip(&game, false);
+    assert_eq!(saved.hints, [Hint::Parity, Hint::Quarter]);
+    assert_eq!(saved.guesses, [50, 30]);
+
+    let restored = saved.restore().unwrap();
+    assert_same(&restored, &game);
+    assert_eq!(restored.points(), game.points());
+}
+
+#[test]
+fn the_attempt_limit_is_kept() {
+    let mut game = Game::new(1..=100, 42).with_max_attempts(3);
+    game.guess(50);
+    game.guess(25);
+
+    let (saved, _) = round_trip(&game, false);
+    let mut restored = saved.restore().unwrap();
+    assert_same(&restored, &game);
+    assert_eq!(restored.attempts_remaining(), Some(1));
+
+    restored.guess(10);
+    assert_eq!(restored.state(), State::Lost);
+}
+
+#[test]
+fn files_without_hints_or_time_still_load() {
+    let path = temporary_file("old");
+    fs::write(
+        &path,
+        r#"{"min": 1, "max": 10, "secret": {"plain": 7}, "max_attempts": null, "guesses": [5]}"#,
+    )
+    .unwrap();
+    let saved = SavedGame::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(saved.elapsed(), Duration::ZERO);
+    let game = saved.restore().unwrap();
+    assert_eq!(game.history().len(), 1);
+    assert!(game.clues().is_empty());
+}
+
+#[test]
+fn broken_saves_are_refused() {
+    let mut saved = SavedGame::from_game(&Game::new(1..=10, 7), false, Duration::ZERO);
+    saved.secret = Secret::Plain(11);
+    assert!(saved.restore().is_err());
+
+    saved.secret = Secret::Obfuscated("not hex".to_string());
+    assert!(saved.restore().is_err());
+
+    saved.secret = Secret::Plain(7);
+    saved.min = 20;
+    assert!(saved.restore().is_err());
+}
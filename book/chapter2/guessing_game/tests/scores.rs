@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use guessing_game::hint::Hint;
+use guessing_game::scores::{self, Score, ScoreFile, Table};
+use guessing_game::Game;
+
+fn temporary_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("guessing-game-scores-{}-{name}", std::process::id()))
+}
+
+fn score(name: &str, attempts: u32, elapsed_ms: u64) -> Score {
+    Score {
+        name: name.to_string(),
+        date: 0,
+        min: 1,
+        max: 100,
+        attempts,
+        elapsed_ms,
+        evil: false,
+        hints: false,
+    }
+}
+
+#[test]
+fn corrupt_and_cut_short_lines_are_skipped() {
+    let path = temporary_file("corrupt");
+    let good = serde_json::to_string(&score("ann", 5, 1000)).unwrap();
+    let cut_short = &good[..good.len() / 2];
+    let old = r#"{"name":"bob","date":0,"min":1,"max":10,"attempts":3,"elapsed_ms":500}"#;
+    let contents = format!("{good}\n{cut_short}\nnot json at all\n\n{{\"name\": 7}}\n\u{fffd}\u{0}\n{old}\n{cut_short}");
+    fs::write(&path, contents).unwrap();
+
+    let loaded = ScoreFile::new(&path).load().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.skipped, 5);
+    assert_eq!(loaded.scores.len(), 2);
+    assert_eq!(loaded.scores[0], score("ann", 5, 1000));
+    // Scores from before the flags were kept count as honest.
+    assert!(!loaded.scores[1].evil && !loaded.scores[1].hints);
+}
+
+#[test]
+fn a_missing_file_is_an_empty_table() {
+    let loaded = ScoreFile::new(temporary_file("missing")).load().unwrap();
+    assert!(loaded.scores.is_empty());
+    assert_eq!(loaded.skipped, 0);
+}
+
+#[test]
+fn appended_scores_load_back() {
+    let path = temporary_file("append");
+    let file = ScoreFile::new(&path);
+    file.append(&score("ann", 5, 1000)).unwrap();
+    file.append(&score("bob", 4, 2000)).unwrap();
+
+    let loaded = file.load().unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.scores, [score("ann", 5, 1000), score("bob", 4, 2000)]);
+}
+
+#[test]
+fn scores_know_whether_the_host_was_evil_or_hints_were_used() {
+    let mut game = Game::new(1..=100, 42);
+    game.guess(42);
+    let honest = Score::new("ann", &game, Duration::from_secs(3));
+    assert_eq!((honest.min, honest.max, honest.attempts), (1, 100, 1));
+    assert_eq!(honest.elapsed_ms, 3000);
+    assert!(!honest.evil && !honest.hints);
+
+    let game = Game::evil(1..=100);
+    let tricked = Score::new("ann", &game, Duration::ZERO);
+    assert!(tricked.evil && !tricked.hints);
+
+    let mut game = Game::new(1..=100, 42);
+    game.hint(Some(Hint::Parity)).unwrap();
+    let helped = Score::new("ann", &game, Duration::ZERO);
+    assert!(!helped.evil && helped.hints);
+}
+
+#[test]
+fn rounds_are_ranked_against_their_own_kind() {
+    let evil = Score {
+        evil: true,
+        ..score("evil", 1, 100)
+    };
+    let hinted = Score {
+        hints: true,
+        ..score("hinted", 2, 100)
+    };
+    let scores = [
+        score("slow", 4, 9000),
+        score("fast", 4, 1000),
+        score("few", 3, 9000),
+        evil.clone(),
+        hinted.clone(),
+    ];
+
+    let tables = scores::best_by_table(&scores, 2);
+    let honest = Table {
+        min: 1,
+        max: 100,
+        evil: false,
+        hints: false,
+    };
+    assert_eq!(tables.len(), 3);
+    assert_eq!(tables[&honest], [score("few", 3, 9000), score("fast", 4, 1000)]);
+    assert_eq!(tables[&evil.table()], [evil]);
+    assert_eq!(tables[&hinted.table()], [hinted]);
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::simulate::simulate;
+use guessing_game::strategy::{StrategyKind, MAX_GUESSES};
+use guessing_game::Game;
+
+// The secret number of round `i`, drawn the way `simulate` draws it.
+fn secret(range: &std::ops::RangeInclusive<u32>, seed: u64, i: u64) -> u32 {
+    Game::from_rng(range.clone(), &mut StdRng::seed_from_u64(seed + i)).secret_number()
+}
+
+#[test]
+fn linear_scan_takes_as_many_attempts_as_the_secret_is_far() {
+    let range = 1..=10;
+    let summary = simulate(StrategyKind::LinearScan, &range, 9, 5, None);
+
+    let mut attempts: Vec<u32> = (0..9).map(|i| secret(&range, 5, i)).collect();
+    attempts.sort_unstable();
+    let mut histogram = BTreeMap::new();
+    for &n in &attempts {
+        *histogram.entry(n).or_insert(0) += 1;
+    }
+
+    assert_eq!(summary.games, 9);
+    assert_eq!(summary.wins, 9);
+    assert_eq!(summary.histogram, histogram);
+    assert_eq!(summary.median, f64::from(attempts[4]));
+    assert_eq!(summary.max, attempts[8]);
+    let total: u32 = attempts.iter().sum();
+    assert!((summary.mean - f64::from(total) / 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn the_median_of_an_even_count_is_between_the_middle_two() {
+    // Linear scan on 1-2 needs one guess for 1 and two for 2.
+    let range = 1..=2;
+    let seed = (0..).find(|&seed| secret(&range, seed, 0) == 1 && secret(&range, seed, 1) == 2).unwrap();
+    let summary = simulate(StrategyKind::LinearScan, &range, 2, seed, None);
+
+    assert_eq!(summary.histogram, BTreeMap::from([(1, 1), (2, 1)]));
+    assert_eq!(summary.median, 1.5);
+    assert_eq!(summary.mean, 1.5);
+    assert_eq!(summary.max, 2);
+}
+
+#[test]
+fn rounds_stop_at_the_attempt_limit() {
+    let range = 1..=100;
+    let summary = simulate(StrategyKind::LinearScan, &range, 50, 3, Some(20));
+    let wins = (0..50).filter(|&i| secret(&range, 3, i) <= 20).count() as u32;
+
+    assert_eq!(summary.wins, wins);
+    assert_eq!(summary.max, 20);
+    // The rounds cut off and the one whose secret is 20 both end at 20.
+    let at_limit = (0..50).filter(|&i| secret(&range, 3, i) >= 20).count() as u32;
+    assert_eq!(summary.histogram.get(&20).copied().unwrap_or(0), at_limit);
+}
+
+#[test]
+fn a_linear_scan_of_a_huge_range_is_cut_off() {
+    let summary = simulate(StrategyKind::LinearScan, &(1..=u32::MAX), 3, 1, None);
+    assert_eq!(summary.max, MAX_GUESSES);
+    let wins = (0..3).filter(|&i| secret(&(1..=u32::MAX), 1, i) <= MAX_GUESSES).count() as u32;
+    assert_eq!(summary.wins, wins);
+}
@@ -0,0 +1,190 @@
+use std::io::{self, BufRead, Read};
+use std::time::Duration;
+
+use guessing_game::cli;
+use guessing_game::report::Plain;
+use guessing_game::timer::{Clock, ManualClock, Timer};
+use guessing_game::{Game, State};
+
+const SECOND: Duration = Duration::from_secs(1);
+
+#[test]
+fn the_timer_counts_from_the_start() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock);
+
+    clock.advance(5 * SECOND);
+    assert_eq!(timer.elapsed(), Duration::ZERO);
+
+    timer.start();
+    clock.advance(3 * SECOND);
+    assert_eq!(timer.elapsed(), 3 * SECOND);
+
+    // Starting again does not reset it.
+    timer.start();
+    clock.advance(SECOND);
+    assert_eq!(timer.elapsed(), 4 * SECOND);
+}
+
+#[test]
+fn the_countdown_runs_out() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock).with_limit(10 * SECOND);
+    assert_eq!(timer.limit(), Some(10 * SECOND));
+
+    timer.start();
+    clock.advance(4 * SECOND);
+    assert_eq!(timer.remaining(), Some(6 * SECOND));
+    assert!(!timer.is_up());
+
+    clock.advance(6 * SECOND);
+    assert_eq!(timer.remaining(), Some(Duration::ZERO));
+    assert!(timer.is_up());
+
+    clock.advance(SECOND);
+    assert_eq!(timer.remaining(), Some(Duration::ZERO));
+    assert!(timer.is_up());
+}
+
+#[test]
+fn without_a_limit_time_is_never_up() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock);
+
+    timer.start();
+    clock.advance(1_000_000 * SECOND);
+    assert_eq!(timer.remaining(), None);
+    assert!(!timer.is_up());
+}
+
+#[test]
+fn splits_are_counted_from_the_start() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock);
+
+    clock.advance(SECOND);
+    timer.start();
+    clock.advance(2 * SECOND);
+    timer.split();
+    clock.advance(500 * Duration::from_millis(1));
+    timer.split();
+
+    assert_eq!(timer.splits(), [2 * SECOND, Duration::from_millis(2500)]);
+}
+
+#[test]
+fn stopping_freezes_the_elapsed_time() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock).with_limit(10 * SECOND);
+
+    timer.start();
+    clock.advance(7 * SECOND);
+    timer.stop();
+    clock.advance(60 * SECOND);
+
+    assert_eq!(timer.elapsed(), 7 * SECOND);
+    assert_eq!(timer.remaining(), Some(3 * SECOND));
+    assert!(!timer.is_up());
+
+    // Only the first stop counts.
+    timer.stop();
+    assert_eq!(timer.elapsed(), 7 * SECOND);
+}
+
+#[test]
+fn a_resumed_timer_goes_on_from_the_time_already_played() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock).with_limit(10 * SECOND).with_elapsed(7 * SECOND);
+    assert_eq!(timer.elapsed(), 7 * SECOND);
+
+    timer.start();
+    clock.advance(2 * SECOND);
+    timer.split();
+    assert_eq!(timer.splits(), [9 * SECOND]);
+    assert!(!timer.is_up());
+
+    clock.advance(SECOND);
+    assert!(timer.is_up());
+}
+
+// Input where the player takes `think` to type every line.
+struct SlowInput<'a> {
+    lines: Vec<&'a str>,
+    // How much of the first line has been read.
+    read: usize,
+    clock: &'a ManualClock,
+    think: Duration,
+    thinking: bool,
+}
+
+impl<'a> SlowInput<'a> {
+    fn new(text: &'a str, clock: &'a ManualClock, think: Duration) -> SlowInput<'a> {
+        SlowInput {
+            lines: text.split_inclusive('\n').collect(),
+            read: 0,
+            clock,
+            think,
+            thinking: true,
+        }
+    }
+}
+
+impl Read for SlowInput<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for SlowInput<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.thinking {
+            self.clock.advance(self.think);
+            self.thinking = false;
+        }
+        Ok(self.lines.first().map_or(&[], |line| &line.as_bytes()[self.read..]))
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read += amount;
+        if self.lines.first().is_some_and(|line| self.read == line.len()) {
+            self.lines.remove(0);
+            self.read = 0;
+            self.thinking = true;
+        }
+    }
+}
+
+#[test]
+fn a_guess_after_the_time_limit_loses_the_round() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock).with_limit(10 * SECOND);
+    let mut game = Game::new(1..=100, 42);
+    let mut output = Vec::new();
+
+    // Four seconds per guess: the third comes in after the ten seconds.
+    let input = SlowInput::new("50\n25\n42\n", &clock, 4 * SECOND);
+    let state = cli::play(&mut game, input, &mut Plain::new(&mut output), &mut timer, None).unwrap();
+
+    assert_eq!(state, State::OutOfTime);
+    assert_eq!(game.attempts(), 2);
+    assert_eq!(timer.splits(), [4 * SECOND, 8 * SECOND]);
+    assert_eq!(clock.now(), 12 * SECOND);
+}
+
+#[test]
+fn a_round_in_time_is_won() {
+    let clock = ManualClock::new();
+    let mut timer = Timer::new(&clock).with_limit(10 * SECOND);
+    let mut game = Game::new(1..=100, 42);
+    let mut output = Vec::new();
+
+    let input = SlowInput::new("50\n25\n42\n", &clock, 3 * SECOND);
+    let state = cli::play(&mut game, input, &mut Plain::new(&mut output), &mut timer, None).unwrap();
+
+    assert_eq!(state, State::Won);
+    assert_eq!(timer.elapsed(), 9 * SECOND);
+}
@@ -0,0 +1,47 @@
+use std::path::Path;
+use std::process::Command;
+
+use guessing_game::transcript::{self, Recording};
+
+// The recorded rounds in tests/golden must still play out exactly as they
+// did when they were recorded.
+#[test]
+fn golden_transcripts_play_back_the_same() {
+    let program = Path::new(env!("CARGO_BIN_EXE_guessing_game"));
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+
+    for entry in golden.read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        let recording = Recording::load(&path).unwrap();
+        let output = recording.rerun(program).unwrap();
+
+        if let Some((line, expected, actual)) = transcript::first_difference(&recording.output(), &output) {
+            panic!(
+                "{} differs at line {line}:\n  recorded: {expected}\n  now:      {actual}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn replay_verify_reports_a_changed_transcript() {
+    let program = env!("CARGO_BIN_EXE_guessing_game");
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/seed_3.transcript");
+    let changed = std::env::temp_dir().join(format!("guessing_game_{}.transcript", std::process::id()));
+    let text = std::fs::read_to_string(golden).unwrap();
+    std::fs::write(&changed, text.replace("You won!", "You lost!")).unwrap();
+
+    let output = Command::new(program)
+        .arg("replay")
+        .arg(&changed)
+        .arg("--verify")
+        .output()
+        .unwrap();
+    std::fs::remove_file(&changed).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("recorded: You lost!"), "{stdout}");
+    assert!(stdout.contains("now:      You won!"), "{stdout}");
+}